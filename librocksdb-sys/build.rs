@@ -37,6 +37,8 @@ fn rocksdb_include_dir() -> String {
 fn bindgen_rocksdb() {
     let bindings = bindgen::Builder::default()
         .header(rocksdb_include_dir() + "/rocksdb/c.h")
+        .header("shim/shim.h")
+        .clang_arg(format!("-I{}", rocksdb_include_dir()))
         .derive_debug(false)
         .blocklist_type("max_align_t") // https://github.com/rust-lang-nursery/rust-bindgen/issues/550
         .ctypes_prefix("libc")
@@ -311,6 +313,28 @@ fn build_rocksdb() {
     config.compile("librocksdb.a");
 }
 
+/// Builds the C functions in `shim/` that the C API lacks, against the
+/// headers of either the bundled or the system RocksDB.
+fn build_shim() {
+    let target = env::var("TARGET").unwrap();
+    let mut config = cc::Build::new();
+    config.include(rocksdb_include_dir());
+    config.include("shim/");
+    config.define("NDEBUG", Some("1"));
+    if target.contains("msvc") {
+        if cfg!(feature = "mt_static") {
+            config.static_crt(true);
+        }
+        config.flag("-EHsc");
+        config.flag("-std:c++17");
+    } else {
+        config.flag(cxx_standard());
+    }
+    config.file("shim/shim.cc");
+    config.cpp(true);
+    config.compile("librocksdb_shim.a");
+}
+
 fn build_snappy() {
     let target = env::var("TARGET").unwrap();
     let endianness = env::var("CARGO_CFG_TARGET_ENDIAN").unwrap();
@@ -401,6 +425,9 @@ fn main() {
         update_submodules();
     }
     bindgen_rocksdb();
+    println!("cargo:rerun-if-changed=shim/");
+    // Linked before RocksDB, which it depends on.
+    build_shim();
     let target = env::var("TARGET").unwrap();
 
     if !try_to_find_and_link_lib("ROCKSDB") {
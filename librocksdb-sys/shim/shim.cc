@@ -0,0 +1,197 @@
+// Copyright 2025 The rust-rocksdb developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "shim.h"
+
+#include <cstddef>
+#include <cstdlib>
+#include <cstring>
+#include <string>
+#include <type_traits>
+
+#include "rocksdb/convenience.h"
+#include "rocksdb/db.h"
+#include "rocksdb/file_checksum.h"
+#include "rocksdb/options.h"
+#include "rocksdb/write_batch.h"
+
+using ROCKSDB_NAMESPACE::ColumnFamilyHandle;
+using ROCKSDB_NAMESPACE::ConfigOptions;
+using ROCKSDB_NAMESPACE::DB;
+using ROCKSDB_NAMESPACE::GetFileChecksumGenCrc32cFactory;
+using ROCKSDB_NAMESPACE::GetStringFromColumnFamilyOptions;
+using ROCKSDB_NAMESPACE::Options;
+using ROCKSDB_NAMESPACE::ReadOptions;
+using ROCKSDB_NAMESPACE::Slice;
+using ROCKSDB_NAMESPACE::Status;
+using ROCKSDB_NAMESPACE::UpdateStatus;
+using ROCKSDB_NAMESPACE::WriteBatch;
+
+namespace {
+
+// The structs behind the opaque types of c.h are defined in db/c.cc. Each
+// holds the wrapped C++ object, or a pointer to it, as its first member
+// `rep`, which is all the shim needs.
+template <typename Rep, typename T>
+Rep& rep(T* wrapper) {
+  return *reinterpret_cast<Rep*>(wrapper);
+}
+
+template <typename Rep, typename T>
+const Rep& rep(const T* wrapper) {
+  return *reinterpret_cast<const Rep*>(wrapper);
+}
+
+// Same as SaveError in db/c.cc.
+bool save_error(char** errptr, const Status& s) {
+  if (s.ok()) {
+    return false;
+  }
+  if (*errptr != nullptr) {
+    free(*errptr);
+  }
+  *errptr = strdup(s.ToString().c_str());
+  return true;
+}
+
+// Forwards the operations of a write batch to C callbacks. The defaults of
+// WriteBatch::Handler fail on everything else, e.g. wide-column puts.
+class CallbackHandler : public WriteBatch::Handler {
+ public:
+  void* state;
+  void (*put_cf)(void*, uint32_t, const char*, size_t, const char*, size_t);
+  void (*deleted_cf)(void*, uint32_t, const char*, size_t);
+  void (*single_deleted_cf)(void*, uint32_t, const char*, size_t);
+  void (*deleted_range_cf)(void*, uint32_t, const char*, size_t, const char*,
+                           size_t);
+  void (*merge_cf)(void*, uint32_t, const char*, size_t, const char*, size_t);
+
+  Status PutCF(uint32_t cfid, const Slice& key, const Slice& value) override {
+    put_cf(state, cfid, key.data(), key.size(), value.data(), value.size());
+    return Status::OK();
+  }
+
+  Status DeleteCF(uint32_t cfid, const Slice& key) override {
+    deleted_cf(state, cfid, key.data(), key.size());
+    return Status::OK();
+  }
+
+  Status SingleDeleteCF(uint32_t cfid, const Slice& key) override {
+    single_deleted_cf(state, cfid, key.data(), key.size());
+    return Status::OK();
+  }
+
+  Status DeleteRangeCF(uint32_t cfid, const Slice& begin_key,
+                       const Slice& end_key) override {
+    deleted_range_cf(state, cfid, begin_key.data(), begin_key.size(),
+                     end_key.data(), end_key.size());
+    return Status::OK();
+  }
+
+  Status MergeCF(uint32_t cfid, const Slice& key, const Slice& value) override {
+    merge_cf(state, cfid, key.data(), key.size(), value.data(), value.size());
+    return Status::OK();
+  }
+};
+
+// rocksdb_shim_inplace_callback_t is installed as inplace_callback, whose
+// parameters and result it must pass the same way.
+static_assert(std::is_standard_layout<Slice>::value &&
+                  std::is_trivially_copyable<Slice>::value,
+              "Slice must be passed like a C struct");
+static_assert(sizeof(Slice) == sizeof(rocksdb_shim_slice_t) &&
+                  offsetof(Slice, data_) ==
+                      offsetof(rocksdb_shim_slice_t, data) &&
+                  offsetof(Slice, size_) ==
+                      offsetof(rocksdb_shim_slice_t, size),
+              "Slice must match rocksdb_shim_slice_t");
+static_assert(sizeof(UpdateStatus) == sizeof(int),
+              "UpdateStatus must be returned as int");
+
+using InplaceCallback = UpdateStatus (*)(char*, uint32_t*, Slice,
+                                         std::string*);
+
+}  // namespace
+
+extern "C" {
+
+void rocksdb_shim_verify_checksum(rocksdb_t* db,
+                                  const rocksdb_readoptions_t* options,
+                                  char** errptr) {
+  save_error(errptr,
+             rep<DB*>(db)->VerifyChecksum(rep<ReadOptions>(options)));
+}
+
+void rocksdb_shim_verify_file_checksums(rocksdb_t* db,
+                                        const rocksdb_readoptions_t* options,
+                                        char** errptr) {
+  save_error(errptr,
+             rep<DB*>(db)->VerifyFileChecksums(rep<ReadOptions>(options)));
+}
+
+char* rocksdb_shim_get_options_cf(
+    rocksdb_t* db, rocksdb_column_family_handle_t* column_family,
+    char** errptr) {
+  DB* rep_db = rep<DB*>(db);
+  std::string options;
+  if (save_error(errptr, GetStringFromColumnFamilyOptions(
+                             ConfigOptions(),
+                             rep_db->GetOptions(
+                                 rep<ColumnFamilyHandle*>(column_family)),
+                             &options))) {
+    return nullptr;
+  }
+  return strdup(options.c_str());
+}
+
+void rocksdb_shim_writebatch_iterate_cf(
+    rocksdb_writebatch_t* b, void* state,
+    void (*put_cf)(void*, uint32_t cfid, const char* k, size_t klen,
+                   const char* v, size_t vlen),
+    void (*deleted_cf)(void*, uint32_t cfid, const char* k, size_t klen),
+    void (*single_deleted_cf)(void*, uint32_t cfid, const char* k,
+                              size_t klen),
+    void (*deleted_range_cf)(void*, uint32_t cfid, const char* begin_key,
+                             size_t begin_klen, const char* end_key,
+                             size_t end_klen),
+    void (*merge_cf)(void*, uint32_t cfid, const char* k, size_t klen,
+                     const char* v, size_t vlen),
+    char** errptr) {
+  CallbackHandler handler;
+  handler.state = state;
+  handler.put_cf = put_cf;
+  handler.deleted_cf = deleted_cf;
+  handler.single_deleted_cf = single_deleted_cf;
+  handler.deleted_range_cf = deleted_range_cf;
+  handler.merge_cf = merge_cf;
+  save_error(errptr, rep<WriteBatch>(b).Iterate(&handler));
+}
+
+void rocksdb_shim_options_set_file_checksum_crc32c(rocksdb_options_t* opt,
+                                                   unsigned char enabled) {
+  rep<Options>(opt).file_checksum_gen_factory =
+      enabled ? GetFileChecksumGenCrc32cFactory() : nullptr;
+}
+
+void rocksdb_shim_options_set_inplace_callback(
+    rocksdb_options_t* opt, rocksdb_shim_inplace_callback_t callback) {
+  rep<Options>(opt).inplace_callback =
+      reinterpret_cast<InplaceCallback>(callback);
+}
+
+void rocksdb_shim_string_assign(void* str, const char* data, size_t len) {
+  static_cast<std::string*>(str)->assign(data, len);
+}
+
+}  // end extern "C"
@@ -0,0 +1,89 @@
+// Copyright 2025 The rust-rocksdb developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// C functions for RocksDB features the C API in rocksdb/c.h doesn't cover.
+// They follow the conventions of c.h: errors are reported through `errptr`
+// as strings to be freed with rocksdb_free.
+
+#pragma once
+
+#include "rocksdb/c.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* DB */
+
+extern void rocksdb_shim_verify_checksum(rocksdb_t* db,
+                                         const rocksdb_readoptions_t* options,
+                                         char** errptr);
+
+extern void rocksdb_shim_verify_file_checksums(
+    rocksdb_t* db, const rocksdb_readoptions_t* options, char** errptr);
+
+/* Returns the options of the column family in effect, serialized as
+   `name=value` pairs separated by ';'. Nested options are enclosed in
+   braces. The result must be freed with rocksdb_free. */
+extern char* rocksdb_shim_get_options_cf(
+    rocksdb_t* db, rocksdb_column_family_handle_t* column_family,
+    char** errptr);
+
+/* Write batch */
+
+/* Like rocksdb_writebatch_iterate_cf, but also reports single deletions and
+   range deletions, and fails on operations none of the callbacks covers. */
+extern void rocksdb_shim_writebatch_iterate_cf(
+    rocksdb_writebatch_t* b, void* state,
+    void (*put_cf)(void*, uint32_t cfid, const char* k, size_t klen,
+                   const char* v, size_t vlen),
+    void (*deleted_cf)(void*, uint32_t cfid, const char* k, size_t klen),
+    void (*single_deleted_cf)(void*, uint32_t cfid, const char* k,
+                              size_t klen),
+    void (*deleted_range_cf)(void*, uint32_t cfid, const char* begin_key,
+                             size_t begin_klen, const char* end_key,
+                             size_t end_klen),
+    void (*merge_cf)(void*, uint32_t cfid, const char* k, size_t klen,
+                     const char* v, size_t vlen),
+    char** errptr);
+
+/* Options */
+
+/* Layout-compatible with rocksdb::Slice. */
+typedef struct rocksdb_shim_slice_t {
+  const char* data;
+  size_t size;
+} rocksdb_shim_slice_t;
+
+/* The signature of ColumnFamilyOptions::inplace_callback, with `merged_value`
+   pointing to a std::string to be set with rocksdb_shim_string_assign. The
+   return value is a rocksdb::UpdateStatus. */
+typedef int (*rocksdb_shim_inplace_callback_t)(char* existing_value,
+                                               uint32_t* existing_value_size,
+                                               rocksdb_shim_slice_t delta_value,
+                                               void* merged_value);
+
+extern void rocksdb_shim_options_set_inplace_callback(
+    rocksdb_options_t* opt, rocksdb_shim_inplace_callback_t callback);
+
+extern void rocksdb_shim_string_assign(void* str, const char* data,
+                                       size_t len);
+
+
+extern void rocksdb_shim_options_set_file_checksum_crc32c(
+    rocksdb_options_t* opt, unsigned char enabled);
+
+#ifdef __cplusplus
+} /* end extern "C" */
+#endif
@@ -17,8 +17,8 @@
 //!
 //! [1]: https://github.com/facebook/rocksdb/wiki/Checkpoints
 
-use crate::{db::DBInner, ffi, ffi_util::to_cpath, DBCommon, Error, ThreadMode};
-use std::{marker::PhantomData, path::Path};
+use crate::{db::DBInner, ffi, ffi_util::to_cpath, DBCommon, Error, ThreadMode, TransactionDB};
+use std::{fs, marker::PhantomData, path::Path};
 
 /// Undocumented parameter for `ffi::rocksdb_checkpoint_create` function. Zero by default.
 const LOG_SIZE_FOR_FLUSH: u64 = 0_u64;
@@ -52,14 +52,84 @@ impl<'db> Checkpoint<'db> {
         })
     }
 
+    /// Creates new checkpoint object for specific transaction DB.
+    ///
+    /// Only committed data is part of the checkpoints it produces.
+    pub fn new_transaction_db<T: ThreadMode>(db: &'db TransactionDB<T>) -> Result<Self, Error> {
+        let checkpoint: *mut ffi::rocksdb_checkpoint_t;
+
+        unsafe {
+            checkpoint = ffi_try!(ffi::rocksdb_transactiondb_checkpoint_object_create(
+                db.inner
+            ));
+        }
+
+        if checkpoint.is_null() {
+            return Err(Error::new("Could not create checkpoint object.".to_owned()));
+        }
+
+        Ok(Self {
+            inner: checkpoint,
+            _db: PhantomData,
+        })
+    }
+
     /// Creates new physical DB checkpoint in directory specified by `path`.
     pub fn create_checkpoint<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        self.create_checkpoint_impl(path, LOG_SIZE_FOR_FLUSH)
+    }
+
+    /// Creates new physical DB checkpoint in directory specified by `path`
+    /// without flushing the memtables first.
+    ///
+    /// The live WAL files are copied into the checkpoint instead, so unflushed
+    /// writes are still part of it and get replayed when the checkpoint is
+    /// opened. This avoids creating small L0 files when checkpoints are taken
+    /// often or during periods of few writes.
+    pub fn create_checkpoint_no_flush<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        self.create_checkpoint_impl(path, u64::MAX)
+    }
+
+    /// Creates new physical DB checkpoint in directory specified by `path` and
+    /// returns the sorted names of the SST and blob files it references.
+    ///
+    /// Those files are hard-linked from the DB when it lives on the same file
+    /// system, so they can be used to skip files a previous backup already
+    /// holds.
+    pub fn create_checkpoint_with_files<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Vec<String>, Error> {
+        let path = path.as_ref();
+        self.create_checkpoint(path)?;
+
+        // The checkpoint directory is created from scratch, so it only holds
+        // the files of this checkpoint.
+        let io_error =
+            |err: std::io::Error| Error::new(format!("Failed to list checkpoint files: {err}"));
+        let mut files = Vec::new();
+        for entry in fs::read_dir(path).map_err(io_error)? {
+            let name = entry.map_err(io_error)?.file_name();
+            let name = name.to_string_lossy();
+            if name.ends_with(".sst") || name.ends_with(".blob") {
+                files.push(name.into_owned());
+            }
+        }
+        files.sort_unstable();
+        Ok(files)
+    }
+
+    fn create_checkpoint_impl<P: AsRef<Path>>(
+        &self,
+        path: P,
+        log_size_for_flush: u64,
+    ) -> Result<(), Error> {
         let cpath = to_cpath(path)?;
         unsafe {
             ffi_try!(ffi::rocksdb_checkpoint_create(
                 self.inner,
                 cpath.as_ptr(),
-                LOG_SIZE_FOR_FLUSH,
+                log_size_for_flush,
             ));
         }
         Ok(())
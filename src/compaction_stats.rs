@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+
+use crate::Error;
+
+/// One row of the per-level compaction stats table of a column family.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LevelCompactionStats {
+    /// Number of SST files in the level
+    pub num_files: u64,
+    /// Number of SST files of the level currently being compacted
+    pub num_files_compacting: u64,
+    /// Compaction score; a level with a score of at least 1 needs compaction
+    pub score: f64,
+    /// GB read by compactions into this level
+    pub read_gb: f64,
+    /// GB written into this level by flushes and compactions
+    pub write_gb: f64,
+    /// Bytes written into the level divided by the bytes read from the level
+    /// above it
+    pub write_amplification: f64,
+    /// Time spent in flushes and compactions, in seconds
+    pub compaction_seconds: f64,
+    /// Number of flushes and compactions
+    pub compaction_count: u64,
+    /// Number of keys read by compactions
+    pub keys_in: u64,
+    /// Number of keys dropped by compactions
+    pub keys_dropped: u64,
+}
+
+/// Compaction stats of a column family, as returned by
+/// [`DBCommon::compaction_stats_cf`](crate::DBCommon::compaction_stats_cf).
+///
+/// The stats are parsed from the table RocksDB prints for the
+/// `rocksdb.cfstats-no-file-histogram` property. Byte counts are reported in
+/// GB with one decimal, so they read as 0 for small amounts of data.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompactionStats {
+    /// Stats of every level that holds files or has seen compactions, keyed
+    /// by level number
+    pub levels: BTreeMap<usize, LevelCompactionStats>,
+    /// Totals over all levels
+    pub sum: LevelCompactionStats,
+}
+
+impl CompactionStats {
+    pub(crate) fn parse(stats: &str) -> Result<Self, Error> {
+        let mut lines = stats
+            .lines()
+            .skip_while(|line| !line.starts_with("** Compaction Stats ["))
+            .skip(1)
+            .skip_while(|line| line.trim().is_empty());
+        let header: Vec<&str> = match lines.next() {
+            Some(header) if header.starts_with("Level") => header.split_whitespace().collect(),
+            _ => return Err(Error::new("Compaction stats table not found".to_owned())),
+        };
+
+        let mut result = CompactionStats::default();
+        for line in lines
+            .skip_while(|line| line.starts_with('-'))
+            .take_while(|line| !line.trim().is_empty())
+        {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let row = Self::parse_row(&header, &tokens)
+                .ok_or_else(|| Error::new(format!("Invalid compaction stats row: {line}")))?;
+            match tokens[0] {
+                "Sum" => result.sum = row,
+                "Int" => {}
+                level => {
+                    let level = level
+                        .strip_prefix('L')
+                        .and_then(|n| n.parse().ok())
+                        .ok_or_else(|| {
+                            Error::new(format!("Invalid compaction stats level: {level}"))
+                        })?;
+                    result.levels.insert(level, row);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    fn parse_row(header: &[&str], tokens: &[&str]) -> Option<LevelCompactionStats> {
+        // "Size" is printed as a value and a unit, so columns after it are
+        // shifted by one token.
+        let column = |name: &str| {
+            let index = header.iter().position(|&column| column == name)?;
+            tokens
+                .get(if index > 2 { index + 1 } else { index })
+                .copied()
+        };
+        let float = |name: &str| column(name)?.parse::<f64>().ok();
+        let count = |name: &str| parse_human_number(column(name)?);
+
+        let (num_files, num_files_compacting) = column("Files")?.split_once('/')?;
+        Some(LevelCompactionStats {
+            num_files: num_files.parse().ok()?,
+            num_files_compacting: num_files_compacting.parse().ok()?,
+            score: float("Score")?,
+            read_gb: float("Read(GB)")?,
+            write_gb: float("Write(GB)")?,
+            write_amplification: float("W-Amp")?,
+            compaction_seconds: float("Comp(sec)")?,
+            compaction_count: count("Comp(cnt)")?,
+            keys_in: count("KeyIn")?,
+            keys_dropped: count("KeyDrop")?,
+        })
+    }
+}
+
+/// Parses counts RocksDB abbreviates with a K, M or G suffix past 10000.
+fn parse_human_number(value: &str) -> Option<u64> {
+    let (digits, multiplier) = match value.as_bytes().last()? {
+        b'K' => (&value[..value.len() - 1], 1_000),
+        b'M' => (&value[..value.len() - 1], 1_000_000),
+        b'G' => (&value[..value.len() - 1], 1_000_000_000),
+        _ => (value, 1),
+    };
+    digits.parse::<u64>().ok().map(|n| n * multiplier)
+}
+
+#[test]
+fn test_parse_compaction_stats() {
+    let stats = "
+** Compaction Stats [default] **
+Level    Files   Size     Score Read(GB)  Rn(GB) Rnp1(GB) Write(GB) Wnew(GB) Moved(GB) W-Amp Rd(MB/s) Wr(MB/s) Comp(sec) CompMergeCPU(sec) Comp(cnt) Avg(sec) KeyIn KeyDrop Rblob(GB) Wblob(GB)
+-------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------
+  L0      1/0   25.30 KB   0.2      0.0     0.0      0.0       0.1      0.1       0.0   1.0      0.0    120.5      0.02              0.01         3    0.007       0      0       0.0       0.0
+  L6      1/1    1.20 MB   0.0      0.2     0.1      0.1       0.2      0.1       0.0   1.8     80.1     75.3      0.05              0.04         1    0.050     12K   2000       0.0       0.0
+ Sum      2/1    1.22 MB   0.0      0.2     0.1      0.1       0.3      0.2       0.0   3.0     60.2    100.4      0.07              0.05         4    0.018     12K   2000       0.0       0.0
+ Int      0/0    0.00 KB   0.0      0.0     0.0      0.0       0.0      0.0       0.0   0.0      0.0      0.0      0.00              0.00         0    0.000       0      0       0.0       0.0
+
+** Compaction Stats [default] **
+Priority    Files   Size     Score Read(GB)
+";
+    let parsed = CompactionStats::parse(stats).unwrap();
+    assert_eq!(parsed.levels.keys().copied().collect::<Vec<_>>(), [0, 6]);
+
+    let l6 = &parsed.levels[&6];
+    assert_eq!((l6.num_files, l6.num_files_compacting), (1, 1));
+    assert_eq!(l6.read_gb, 0.2);
+    assert_eq!(l6.write_gb, 0.2);
+    assert_eq!(l6.write_amplification, 1.8);
+    assert_eq!(l6.compaction_seconds, 0.05);
+    assert_eq!(l6.compaction_count, 1);
+    assert_eq!((l6.keys_in, l6.keys_dropped), (12_000, 2000));
+
+    assert_eq!(parsed.levels[&0].score, 0.2);
+    assert_eq!(parsed.sum.num_files, 2);
+    assert_eq!(parsed.sum.write_amplification, 3.0);
+    assert_eq!(parsed.sum.compaction_count, 4);
+
+    assert!(CompactionStats::parse("").is_err());
+}
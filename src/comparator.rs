@@ -115,3 +115,47 @@ impl ComparatorWithTsCallback {
         (cb.compare_without_ts_fn)(a, a_has_ts, b, b_has_ts) as c_int
     }
 }
+
+/// Key ordering of RocksDB's built-in `BytewiseComparatorWithU64Ts`: keys are
+/// compared bytewise without their timestamp, and equal keys are ordered by
+/// descending `u64` little endian timestamp, i.e. newest first.
+///
+/// These functions run inside callbacks from RocksDB, where a panic aborts the
+/// process, so keys and timestamps too short to hold a timestamp are compared
+/// as raw bytes instead.
+pub(crate) mod u64_ts {
+    use std::cmp::Ordering;
+
+    pub const NAME: &str = "leveldb.BytewiseComparator.u64ts";
+
+    pub const TIMESTAMP_SIZE: usize = 8;
+
+    pub fn compare(a: &[u8], b: &[u8]) -> Ordering {
+        compare_without_ts(a, true, b, true).then_with(|| compare_ts(ts(a), ts(b)).reverse())
+    }
+
+    pub fn compare_ts(a_ts: &[u8], b_ts: &[u8]) -> Ordering {
+        match (decode(a_ts), decode(b_ts)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => a_ts.cmp(b_ts),
+        }
+    }
+
+    pub fn compare_without_ts(a: &[u8], a_has_ts: bool, b: &[u8], b_has_ts: bool) -> Ordering {
+        let a = if a_has_ts { strip_ts(a) } else { a };
+        let b = if b_has_ts { strip_ts(b) } else { b };
+        a.cmp(b)
+    }
+
+    fn ts(key: &[u8]) -> &[u8] {
+        &key[key.len().saturating_sub(TIMESTAMP_SIZE)..]
+    }
+
+    fn strip_ts(key: &[u8]) -> &[u8] {
+        &key[..key.len().saturating_sub(TIMESTAMP_SIZE)]
+    }
+
+    fn decode(ts: &[u8]) -> Option<u64> {
+        ts.try_into().ok().map(u64::from_le_bytes)
+    }
+}
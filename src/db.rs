@@ -20,16 +20,18 @@ use crate::{
     db_options::OptionsMustOutliveDB,
     ffi,
     ffi_util::{from_cstr, opt_bytes_to_ptr, raw_data, to_cpath, CStrLike},
-    ColumnFamily, ColumnFamilyDescriptor, CompactOptions, DBIteratorWithThreadMode,
-    DBPinnableSlice, DBRawIteratorWithThreadMode, DBWALIterator, Direction, Error, FlushOptions,
-    IngestExternalFileOptions, IteratorMode, Options, ReadOptions, SnapshotWithThreadMode,
-    WaitForCompactOptions, WriteBatch, WriteOptions, DEFAULT_COLUMN_FAMILY_NAME,
+    properties, ColumnFamily, ColumnFamilyDescriptor, CompactOptions, CompactionStats,
+    DBIteratorWithThreadMode, DBKeyIteratorWithThreadMode, DBPinnableSlice,
+    DBRawIteratorWithThreadMode, DBWALIterator, Direction, Error, FlushOptions,
+    IngestExternalFileOptions, IteratorMode, Options, PerfContext, PerfContextSnapshot,
+    ReadOptions, SnapshotWithThreadMode, WaitForCompactOptions, WriteBatch, WriteOptions,
+    DEFAULT_COLUMN_FAMILY_NAME,
 };
 
 use crate::column_family::ColumnFamilyTtl;
 use crate::ffi_util::CSlice;
 use libc::{self, c_char, c_int, c_uchar, c_void, size_t};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::{CStr, CString};
 use std::fmt;
 use std::fs;
@@ -40,6 +42,7 @@ use std::ptr;
 use std::slice;
 use std::str;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::RwLock;
 use std::time::Duration;
 
@@ -60,6 +63,12 @@ pub trait ThreadMode {
     ) -> Self;
     /// Internal implementation for dropping column family handles
     fn drop_all_cfs_internal(&mut self);
+    /// Internal implementation for passing the handles of all column
+    /// families to `f`, which are kept alive until it returns
+    fn with_all_cfs_internal<R>(
+        &self,
+        f: impl FnOnce(&mut [*mut ffi::rocksdb_column_family_handle_t]) -> R,
+    ) -> R;
 }
 
 /// Actual marker type for the marker trait `ThreadMode`, which holds
@@ -97,6 +106,14 @@ impl ThreadMode for SingleThreaded {
         // Cause all ColumnFamily objects to be Drop::drop()-ed.
         self.cfs.clear();
     }
+
+    fn with_all_cfs_internal<R>(
+        &self,
+        f: impl FnOnce(&mut [*mut ffi::rocksdb_column_family_handle_t]) -> R,
+    ) -> R {
+        let mut handles: Vec<_> = self.cfs.values().map(|cf| cf.inner).collect();
+        f(&mut handles)
+    }
 }
 
 impl ThreadMode for MultiThreaded {
@@ -116,6 +133,17 @@ impl ThreadMode for MultiThreaded {
         // Cause all UnboundColumnFamily objects to be Drop::drop()-ed.
         self.cfs.write().unwrap().clear();
     }
+
+    fn with_all_cfs_internal<R>(
+        &self,
+        f: impl FnOnce(&mut [*mut ffi::rocksdb_column_family_handle_t]) -> R,
+    ) -> R {
+        // Hold the handles rather than the map lock, so that `create_cf` and
+        // `drop_cf` aren't blocked while `f` runs.
+        let cfs: Vec<_> = self.cfs.read().unwrap().values().cloned().collect();
+        let mut handles: Vec<_> = cfs.iter().map(|cf| cf.inner).collect();
+        f(&mut handles)
+    }
 }
 
 /// Get underlying `rocksdb_t`.
@@ -128,10 +156,13 @@ pub trait DBInner {
 ///
 /// [`OptimisticTransactionDB`]: crate::OptimisticTransactionDB
 pub struct DBCommon<T: ThreadMode, D: DBInner> {
+    // Declared before `inner` so that it's dropped before the DB is closed.
+    default_cf: ColumnFamily,
     pub(crate) inner: D,
     cfs: T, // Column families are held differently depending on thread mode
     path: PathBuf,
-    _outlive: Vec<OptionsMustOutliveDB>,
+    // Grows with the options of column families created after opening.
+    outlive: Mutex<Vec<OptionsMustOutliveDB>>,
 }
 
 /// Minimal set of DB-related methods, intended to be generic over
@@ -379,6 +410,10 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
 
     /// Opens the database with a Time to Live compaction filter.
     ///
+    /// Entries older than `ttl` (in whole seconds) are removed when a
+    /// compaction processes them. Until then, reads may still return expired
+    /// entries.
+    ///
     /// This applies the given `ttl` to all column families created without an explicit TTL.
     /// See [`DB::open_cf_descriptors_with_ttl`] for more control over individual column family TTLs.
     pub fn open_with_ttl<P: AsRef<Path>>(
@@ -694,10 +729,11 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
         }
 
         Ok(Self {
+            default_cf: default_column_family(db),
             inner: DBWithThreadModeInner { inner: db },
             path: path.as_ref().to_path_buf(),
             cfs: T::new_cf_map_internal(cf_map),
-            _outlive: outlive,
+            outlive: Mutex::new(outlive),
         })
     }
 
@@ -857,16 +893,92 @@ impl<T: ThreadMode> DBWithThreadMode<T> {
         wo.disable_wal(true);
         self.write_opt(batch, &wo)
     }
+
+    /// Writes all `(key, value)` pairs of `items` atomically, as a single
+    /// [`WriteBatch`]. Either every pair is written or, on error, none are.
+    pub fn put_many<I, K, V>(&self, items: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.write(put_many_batch(items, |batch, key, value| {
+            batch.put(key, value)
+        }))
+    }
+
+    /// Same as `put_many` but writes into the specified column family.
+    pub fn put_many_cf<I, K, V>(&self, cf: &impl AsColumnFamilyRef, items: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.write(put_many_batch(items, |batch, key, value| {
+            batch.put_cf(cf, key, value)
+        }))
+    }
+
+    /// Removes the entries of every `[from, to)` range of `ranges` from the
+    /// specified column family atomically, as a single [`WriteBatch`].
+    pub fn delete_ranges_cf<I, K>(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        ranges: I,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = (K, K)>,
+        K: AsRef<[u8]>,
+    {
+        let mut batch = WriteBatch::default();
+        for (from, to) in ranges {
+            batch.delete_range_cf(cf, from, to);
+        }
+        self.write(batch)
+    }
+
+    /// Deletes every key of the specified column family while keeping the
+    /// column family, and so its handles, in place.
+    ///
+    /// The keys are removed with a single range deletion spanning the first to
+    /// the last key, written atomically in one [`WriteBatch`]. Keys written
+    /// concurrently past the last key seen may survive. The deleted data only
+    /// frees disk space once compacted; pass `compact` to compact the column
+    /// family right away.
+    pub fn truncate_cf(&self, cf: &impl AsColumnFamilyRef, compact: bool) -> Result<(), Error> {
+        let mut iter = self.raw_iterator_cf(cf);
+        iter.seek_to_first();
+        let first = iter.key().map(<[u8]>::to_vec);
+        iter.seek_to_last();
+        let last = iter.key().map(<[u8]>::to_vec);
+        iter.status()?;
+        drop(iter);
+
+        if let (Some(first), Some(last)) = (first, last) {
+            let mut batch = WriteBatch::default();
+            // The end of a range deletion is exclusive.
+            if first != last {
+                batch.delete_range_cf(cf, &first, &last);
+            }
+            batch.delete_cf(cf, &last);
+            self.write(batch)?;
+        }
+        if compact {
+            self.compact_range_cf(cf, None::<&[u8]>, None::<&[u8]>);
+        }
+        Ok(())
+    }
 }
 
 /// Common methods of `DBWithThreadMode` and `OptimisticTransactionDB`.
 impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
     pub(crate) fn new(inner: D, cfs: T, path: PathBuf, outlive: Vec<OptionsMustOutliveDB>) -> Self {
         Self {
+            default_cf: default_column_family(inner.inner()),
             inner,
             cfs,
             path,
-            _outlive: outlive,
+            outlive: Mutex::new(outlive),
         }
     }
 
@@ -979,6 +1091,64 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
         self.flush_cf_opt(cf, &FlushOptions::default())
     }
 
+    /// Flushes multiple column families using default options.
+    ///
+    /// With [`Options::set_atomic_flush`] enabled, the listed column families are flushed
+    /// atomically. See [`flush_cfs_opt`](Self::flush_cfs_opt).
+    pub fn flush_cfs(&self, cfs: &[&impl AsColumnFamilyRef]) -> Result<(), Error> {
+        self.flush_cfs_opt(cfs, &FlushOptions::default())
+    }
+
+    /// Brings the database into a consistent on-disk state in preparation for
+    /// closing it.
+    ///
+    /// The steps are performed in this order:
+    ///
+    /// 1. All column families are flushed and the call waits for the flush to
+    ///    finish. If [`Options::set_atomic_flush`] is enabled, the flush is
+    ///    atomic across column families, i.e. all of them are persisted up to
+    ///    the same sequence number.
+    /// 2. The WAL is flushed and synced, so that writes issued after the
+    ///    memtables were switched are durable as well.
+    /// 3. If `cancel_background_work` is `true`, all background work is
+    ///    cancelled and the call waits for running jobs to stop. The database
+    ///    must not be written to after this, only dropped.
+    ///
+    /// Flushing before syncing the WAL matters: once the memtables are
+    /// persisted they no longer depend on the WAL, and syncing afterwards
+    /// makes durable any writes that landed in the new WAL while the flush
+    /// was running.
+    ///
+    /// Column families created while the flush is running are not flushed.
+    pub fn checkpoint_consistent_shutdown(
+        &self,
+        cancel_background_work: bool,
+    ) -> Result<(), Error> {
+        let mut flushopts = FlushOptions::default();
+        flushopts.set_wait(true);
+        self.cfs.with_all_cfs_internal(|cfs| {
+            if cfs.is_empty() {
+                // Opened without explicit column families: only the default
+                // one exists.
+                return self.flush_opt(&flushopts);
+            }
+            unsafe {
+                ffi_try!(ffi::rocksdb_flush_cfs(
+                    self.inner.inner(),
+                    flushopts.inner,
+                    cfs.as_mut_ptr(),
+                    cfs.len() as libc::c_int,
+                ));
+            }
+            Ok(())
+        })?;
+        self.flush_wal(true)?;
+        if cancel_background_work {
+            self.cancel_all_background_work(true);
+        }
+        Ok(())
+    }
+
     /// Return the bytes associated with a key value with read options. If you only intend to use
     /// the vector returned temporarily, consider using [`get_pinned_opt`](#method.get_pinned_opt)
     /// to avoid unnecessary memory copy.
@@ -1105,6 +1275,87 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
         self.get_pinned_cf_opt(cf, key, &ReadOptions::default())
     }
 
+    /// Same as `get_pinned_opt`, but also returns the perf counters of the
+    /// calling thread accumulated by this read, such as
+    /// [`PerfMetric::BlockReadCount`] or [`PerfMetric::BloomSstMissCount`]
+    /// (the key was ruled out by a bloom filter).
+    ///
+    /// Perf counters are only collected if enabled for the calling thread with
+    /// [`set_perf_stats`](crate::perf::set_perf_stats); otherwise all counters
+    /// are 0.
+    ///
+    /// [`PerfMetric::BlockReadCount`]: crate::PerfMetric::BlockReadCount
+    /// [`PerfMetric::BloomSstMissCount`]: crate::PerfMetric::BloomSstMissCount
+    pub fn get_pinned_with_stats<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> (Result<Option<DBPinnableSlice>, Error>, PerfContextSnapshot) {
+        let ctx = PerfContext::default();
+        let before = ctx.snapshot();
+        let result = self.get_pinned_opt(key, readopts);
+        (result, ctx.snapshot().diff(&before))
+    }
+
+    /// Same as `get_pinned_with_stats` but for the specified column family.
+    pub fn get_pinned_cf_with_stats<K: AsRef<[u8]>>(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> (Result<Option<DBPinnableSlice>, Error>, PerfContextSnapshot) {
+        let ctx = PerfContext::default();
+        let before = ctx.snapshot();
+        let result = self.get_pinned_cf_opt(cf, key, readopts);
+        (result, ctx.snapshot().diff(&before))
+    }
+
+    /// Copies the value associated with a key into `buffer`, avoiding an
+    /// allocation per read when the same buffer is reused.
+    ///
+    /// Returns [`GetIntoBufferResult::BufferTooSmall`] with the size of the
+    /// value, leaving `buffer` untouched, if the value does not fit.
+    pub fn get_into_buffer<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        buffer: &mut [u8],
+    ) -> Result<GetIntoBufferResult, Error> {
+        self.get_into_buffer_opt(key, buffer, &ReadOptions::default())
+    }
+
+    /// Same as `get_into_buffer` but uses the provided read options.
+    pub fn get_into_buffer_opt<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        buffer: &mut [u8],
+        readopts: &ReadOptions,
+    ) -> Result<GetIntoBufferResult, Error> {
+        let value = self.get_pinned_opt(key, readopts)?;
+        Ok(copy_into_buffer(value.as_deref(), buffer))
+    }
+
+    /// Same as `get_into_buffer` but for the specified column family.
+    pub fn get_into_buffer_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        key: K,
+        buffer: &mut [u8],
+    ) -> Result<GetIntoBufferResult, Error> {
+        self.get_into_buffer_cf_opt(cf, key, buffer, &ReadOptions::default())
+    }
+
+    /// Same as `get_into_buffer_cf` but uses the provided read options.
+    pub fn get_into_buffer_cf_opt<K: AsRef<[u8]>>(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        key: K,
+        buffer: &mut [u8],
+        readopts: &ReadOptions,
+    ) -> Result<GetIntoBufferResult, Error> {
+        let value = self.get_pinned_cf_opt(cf, key, readopts)?;
+        Ok(copy_into_buffer(value.as_deref(), buffer))
+    }
+
     /// Return the values associated with the given keys.
     pub fn multi_get<K, I>(&self, keys: I) -> Vec<Result<Option<Vec<u8>>, Error>>
     where
@@ -1212,6 +1463,27 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
         convert_values(values, values_sizes, errors)
     }
 
+    /// Same as `multi_get_cf` but pairs every result with the name of the
+    /// column family and the key it was looked up with, in input order.
+    ///
+    /// Useful for diagnosing lookups that span several column families.
+    pub fn multi_get_cf_labeled<'a, 'b: 'a, K, I, W>(
+        &'a self,
+        keys: I,
+    ) -> Vec<(String, K, Result<Option<Vec<u8>>, Error>)>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (&'b W, K)>,
+        W: 'b + AsColumnFamilyRef,
+    {
+        let keys: Vec<_> = keys.into_iter().collect();
+        let results = self.multi_get_cf(keys.iter().map(|(cf, key)| (*cf, key)));
+        keys.into_iter()
+            .zip(results)
+            .map(|((cf, key), result)| (column_family_name(cf.inner()), key, result))
+            .collect()
+    }
+
     /// Return the values associated with the given keys and the specified column family
     /// where internally the read requests are processed in batch if block-based table
     /// SST format is used.  It is a more optimized version of multi_get_cf.
@@ -1241,6 +1513,55 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
     where
         K: AsRef<[u8]> + 'a + ?Sized,
         I: IntoIterator<Item = &'a K>,
+    {
+        let mut values = Vec::new();
+        self.batched_multi_get_raw(cf.inner(), keys, sorted_input, readopts, &mut values);
+        values
+    }
+
+    /// Same as `batched_multi_get_cf` for the default column family, but
+    /// writes the results into `out` instead of allocating a new `Vec`.
+    ///
+    /// `out` is cleared first, so its allocation can be reused across calls
+    /// in tight loops. Only the output buffer is reused: the key and result
+    /// arrays handed to RocksDB are still allocated on every call.
+    pub fn multi_get_pinned_into<'a, 'd, K, I>(
+        &'d self,
+        keys: I,
+        sorted_input: bool,
+        out: &mut Vec<Result<Option<DBPinnableSlice<'d>>, Error>>,
+    ) where
+        K: AsRef<[u8]> + 'a + ?Sized,
+        I: IntoIterator<Item = &'a K>,
+    {
+        self.multi_get_pinned_into_opt(keys, sorted_input, &ReadOptions::default(), out);
+    }
+
+    /// Same as `multi_get_pinned_into` but with the given read options.
+    pub fn multi_get_pinned_into_opt<'a, 'd, K, I>(
+        &'d self,
+        keys: I,
+        sorted_input: bool,
+        readopts: &ReadOptions,
+        out: &mut Vec<Result<Option<DBPinnableSlice<'d>>, Error>>,
+    ) where
+        K: AsRef<[u8]> + 'a + ?Sized,
+        I: IntoIterator<Item = &'a K>,
+    {
+        out.clear();
+        self.batched_multi_get_raw(self.default_cf.inner, keys, sorted_input, readopts, out);
+    }
+
+    fn batched_multi_get_raw<'a, 'd, K, I>(
+        &'d self,
+        cf: *mut ffi::rocksdb_column_family_handle_t,
+        keys: I,
+        sorted_input: bool,
+        readopts: &ReadOptions,
+        out: &mut Vec<Result<Option<DBPinnableSlice<'d>>, Error>>,
+    ) where
+        K: AsRef<[u8]> + 'a + ?Sized,
+        I: IntoIterator<Item = &'a K>,
     {
         let (ptr_keys, keys_sizes): (Vec<_>, Vec<_>) = keys
             .into_iter()
@@ -1257,7 +1578,7 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
             ffi::rocksdb_batched_multi_get_cf(
                 self.inner.inner(),
                 readopts.inner,
-                cf.inner(),
+                cf,
                 ptr_keys.len(),
                 ptr_keys.as_ptr(),
                 keys_sizes.as_ptr(),
@@ -1265,24 +1586,56 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
                 errors.as_mut_ptr(),
                 sorted_input,
             );
-            pinned_values
-                .into_iter()
-                .zip(errors)
-                .map(|(v, e)| {
-                    if e.is_null() {
-                        if v.is_null() {
-                            Ok(None)
-                        } else {
-                            Ok(Some(DBPinnableSlice::from_c(v)))
-                        }
+            out.extend(pinned_values.into_iter().zip(errors).map(|(v, e)| {
+                if e.is_null() {
+                    if v.is_null() {
+                        Ok(None)
                     } else {
-                        Err(Error::new(crate::ffi_util::error_message(e)))
+                        Ok(Some(DBPinnableSlice::from_c(v)))
                     }
-                })
-                .collect()
+                } else {
+                    Err(Error::new(crate::ffi_util::error_message(e)))
+                }
+            }));
         }
     }
 
+    /// Looks up groups of keys in several column families at once, issuing one
+    /// batched request per column family as in `batched_multi_get_cf`.
+    ///
+    /// The result holds one vector per input group, in input order, each
+    /// aligned with the keys of its group.
+    pub fn batched_multi_get_multi_cf<'b, K, I, W>(
+        &self,
+        cfs_and_keys: I,
+        sorted_input: bool,
+    ) -> Vec<Vec<Result<Option<DBPinnableSlice>, Error>>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (&'b W, Vec<K>)>,
+        W: 'b + AsColumnFamilyRef,
+    {
+        self.batched_multi_get_multi_cf_opt(cfs_and_keys, sorted_input, &ReadOptions::default())
+    }
+
+    /// Same as `batched_multi_get_multi_cf` but uses the provided read options.
+    pub fn batched_multi_get_multi_cf_opt<'b, K, I, W>(
+        &self,
+        cfs_and_keys: I,
+        sorted_input: bool,
+        readopts: &ReadOptions,
+    ) -> Vec<Vec<Result<Option<DBPinnableSlice>, Error>>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (&'b W, Vec<K>)>,
+        W: 'b + AsColumnFamilyRef,
+    {
+        cfs_and_keys
+            .into_iter()
+            .map(|(cf, keys)| self.batched_multi_get_cf_opt(cf, &keys, sorted_input, readopts))
+            .collect()
+    }
+
     /// Returns `false` if the given key definitely doesn't exist in the database, otherwise returns
     /// `true`. This function uses default `ReadOptions`.
     pub fn key_may_exist<K: AsRef<[u8]>>(&self, key: K) -> bool {
@@ -1339,6 +1692,28 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
         }
     }
 
+    /// Checks every key of `keys` with
+    /// [`key_may_exist_cf_opt`](Self::key_may_exist_cf_opt), returning the
+    /// results in the same order.
+    ///
+    /// `false` means the key definitely doesn't exist in the column family,
+    /// `true` that it may, for instance because a bloom filter could not rule
+    /// it out. This can be used to skip reads of keys known to be absent.
+    pub fn multi_key_may_exist_cf<K, I>(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        keys: I,
+        readopts: &ReadOptions,
+    ) -> Vec<bool>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        keys.into_iter()
+            .map(|key| self.key_may_exist_cf_opt(cf, key, readopts))
+            .collect()
+    }
+
     /// If the key definitely does not exist in the database, then this method
     /// returns `(false, None)`, else `(true, None)` if it may.
     /// If the key is found in memory, then it returns `(true, Some<CSlice>)`.
@@ -1392,13 +1767,15 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
                 "Failed to convert path to CString when creating cf: {err}"
             ))
         })?;
-        Ok(unsafe {
+        let inner = unsafe {
             ffi_try!(ffi::rocksdb_create_column_family(
                 self.inner.inner(),
                 opts.inner,
                 cf_name.as_ptr(),
             ))
-        })
+        };
+        self.outlive.lock().unwrap().push(opts.outlive.clone());
+        Ok(inner)
     }
 
     pub fn iterator<'a: 'b, 'b>(
@@ -1472,6 +1849,25 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
         DBIteratorWithThreadMode::new_cf(self, cf_handle.inner(), opts, mode)
     }
 
+    /// Opens an iterator over the default column family that yields only keys.
+    /// See [`DBKeyIteratorWithThreadMode`] for details.
+    pub fn key_iterator<'a: 'b, 'b>(
+        &'a self,
+        mode: IteratorMode,
+    ) -> DBKeyIteratorWithThreadMode<'b, Self> {
+        DBKeyIteratorWithThreadMode::new(self.iterator(mode))
+    }
+
+    /// Opens an iterator over the given column family that yields only keys.
+    /// See [`DBKeyIteratorWithThreadMode`] for details.
+    pub fn key_iterator_cf<'a: 'b, 'b>(
+        &'a self,
+        cf_handle: &impl AsColumnFamilyRef,
+        mode: IteratorMode,
+    ) -> DBKeyIteratorWithThreadMode<'b, Self> {
+        DBKeyIteratorWithThreadMode::new(self.iterator_cf(cf_handle, mode))
+    }
+
     pub fn prefix_iterator_cf<'a, P: AsRef<[u8]>>(
         &'a self,
         cf_handle: &impl AsColumnFamilyRef,
@@ -2008,6 +2404,12 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
         Ok(())
     }
 
+    /// Changes mutable options of the default column family on the live DB,
+    /// e.g. `write_buffer_size` or `disable_auto_compactions`.
+    ///
+    /// Options are given as `(name, value)` pairs using the same names and
+    /// value syntax as the options file. Unknown or immutable options, as well
+    /// as invalid values, result in an error and leave the options unchanged.
     pub fn set_options(&self, opts: &[(&str, &str)]) -> Result<(), Error> {
         let copts = convert_options(opts)?;
         let cnames: Vec<*const c_char> = copts.iter().map(|opt| opt.0.as_ptr()).collect();
@@ -2024,6 +2426,7 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
         Ok(())
     }
 
+    /// Same as `set_options` but for the specified column family.
     pub fn set_options_cf(
         &self,
         cf: &impl AsColumnFamilyRef,
@@ -2045,6 +2448,42 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
         Ok(())
     }
 
+    /// Returns the options of the default column family currently in effect,
+    /// including any adjustments RocksDB made while sanitizing them and
+    /// changes made with `set_options`, as `(name, value)` pairs in the same
+    /// syntax `set_options` accepts.
+    ///
+    /// Options holding other objects, such as the table factory, have their
+    /// own options as value, enclosed in braces. Objects that can't be
+    /// serialized, such as a custom comparator or merge operator, are
+    /// reported by name.
+    pub fn get_options(&self) -> Result<HashMap<String, String>, Error> {
+        self.get_options_raw(self.default_cf.inner)
+    }
+
+    /// Same as `get_options` but for the specified column family.
+    pub fn get_options_cf(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+    ) -> Result<HashMap<String, String>, Error> {
+        self.get_options_raw(cf.inner())
+    }
+
+    fn get_options_raw(
+        &self,
+        cf: *mut ffi::rocksdb_column_family_handle_t,
+    ) -> Result<HashMap<String, String>, Error> {
+        let value = unsafe { ffi_try!(ffi::rocksdb_shim_get_options_cf(self.inner.inner(), cf)) };
+        let options = unsafe { CStr::from_ptr(value) }
+            .to_str()
+            .map(parse_options_string)
+            .map_err(|e| Error::new(format!("Failed to convert options to string: {e}")));
+        unsafe {
+            ffi::rocksdb_free(value as *mut c_void);
+        }
+        options
+    }
+
     /// Implementation for property_value et al methods.
     ///
     /// `name` is the name of the property.  It will be converted into a CString
@@ -2149,7 +2588,167 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
         )
     }
 
+    /// Reads the commonly monitored integer properties of the specified column
+    /// family in one call. See [`CfStats`].
+    pub fn cf_stats(&self, cf: &impl AsColumnFamilyRef) -> Result<CfStats, Error> {
+        let stat = |name: &CStr| {
+            self.property_int_value_cf(cf, name)
+                .map(Option::unwrap_or_default)
+        };
+        Ok(CfStats {
+            num_immutable_mem_table: stat(properties::NUM_IMMUTABLE_MEM_TABLE)?,
+            mem_table_flush_pending: stat(properties::MEM_TABLE_FLUSH_PENDING)?,
+            num_running_compactions: stat(properties::NUM_RUNNING_COMPACTIONS)?,
+            estimate_num_keys: stat(properties::ESTIMATE_NUM_KEYS)?,
+            estimate_live_data_size: stat(properties::ESTIMATE_LIVE_DATA_SIZE)?,
+            level0_file_count: stat(&properties::num_files_at_level(0))?,
+        })
+    }
+
+    /// Returns the per-level compaction stats of the default column family,
+    /// such as bytes read and written and write amplification. See
+    /// [`CompactionStats`].
+    pub fn compaction_stats(&self) -> Result<CompactionStats, Error> {
+        self.property_value(properties::CFSTATS_NO_FILE_HISTOGRAM)?
+            .ok_or_else(|| Error::new("Compaction stats are not available".to_owned()))
+            .and_then(|stats| CompactionStats::parse(&stats))
+    }
+
+    /// Same as `compaction_stats` but for the specified column family.
+    pub fn compaction_stats_cf(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+    ) -> Result<CompactionStats, Error> {
+        self.property_value_cf(cf, properties::CFSTATS_NO_FILE_HISTOGRAM)?
+            .ok_or_else(|| Error::new("Compaction stats are not available".to_owned()))
+            .and_then(|stats| CompactionStats::parse(&stats))
+    }
+
+    /// Returns the number of levels of the default column family, as
+    /// configured by [`Options::set_num_levels`].
+    pub fn number_levels(&self) -> Result<i32, Error> {
+        Self::level_file_counts(|level| {
+            self.property_int_value(&properties::num_files_at_level(level))
+        })
+        .map(|counts| counts.len() as i32)
+    }
+
+    /// Same as `number_levels` but for the specified column family.
+    pub fn number_levels_cf(&self, cf: &impl AsColumnFamilyRef) -> Result<i32, Error> {
+        Self::level_file_counts(|level| {
+            self.property_int_value_cf(cf, &properties::num_files_at_level(level))
+        })
+        .map(|counts| counts.len() as i32)
+    }
+
+    /// Returns the highest level of the default column family that holds at
+    /// least one SST file, or `None` if the column family has no files.
+    pub fn max_level_with_files(&self) -> Result<Option<i32>, Error> {
+        Self::level_file_counts(|level| {
+            self.property_int_value(&properties::num_files_at_level(level))
+        })
+        .map(|counts| counts.iter().rposition(|&n| n > 0).map(|l| l as i32))
+    }
+
+    /// Same as `max_level_with_files` but for the specified column family.
+    pub fn max_level_with_files_cf(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+    ) -> Result<Option<i32>, Error> {
+        Self::level_file_counts(|level| {
+            self.property_int_value_cf(cf, &properties::num_files_at_level(level))
+        })
+        .map(|counts| counts.iter().rposition(|&n| n > 0).map(|l| l as i32))
+    }
+
+    /// Returns the compression ratio (uncompressed data size / compressed file
+    /// size) of the SST files at `level` of the default column family.
+    ///
+    /// Returns `None` if the level holds no files or is past the configured
+    /// number of levels.
+    pub fn compression_ratio_at_level(&self, level: usize) -> Result<Option<f64>, Error> {
+        Self::property_value_impl(
+            &properties::compression_ratio_at_level(level),
+            |prop_name| unsafe { ffi::rocksdb_property_value(self.inner.inner(), prop_name) },
+            Self::parse_property_ratio_value,
+        )
+        .map(Option::flatten)
+    }
+
+    /// Same as `compression_ratio_at_level` but for the specified column family.
+    pub fn compression_ratio_at_level_cf(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        level: usize,
+    ) -> Result<Option<f64>, Error> {
+        Self::property_value_impl(
+            &properties::compression_ratio_at_level(level),
+            |prop_name| unsafe {
+                ffi::rocksdb_property_value_cf(self.inner.inner(), cf.inner(), prop_name)
+            },
+            Self::parse_property_ratio_value,
+        )
+        .map(Option::flatten)
+    }
+
+    /// Returns whether writes are currently stopped, i.e. blocked until
+    /// flushes or compactions catch up.
+    ///
+    /// Write stalls are tracked for the whole database: any column family
+    /// exceeding its `level0_stop_writes_trigger`, `max_write_buffer_number` or
+    /// `hard_pending_compaction_bytes_limit` stops writes to all of them.
+    pub fn is_write_stopped(&self) -> Result<bool, Error> {
+        Ok(self
+            .property_int_value(properties::IS_WRITE_STOPPED)?
+            .is_some_and(|stopped| stopped != 0))
+    }
+
+    /// Returns whether writes are currently delayed or stopped.
+    ///
+    /// Writes are delayed once a column family reaches a slowdown condition
+    /// such as `level0_slowdown_writes_trigger`, before RocksDB stops them
+    /// altogether; see [`is_write_stopped`](Self::is_write_stopped). Writers
+    /// can use this to back off early.
+    pub fn is_write_stalled(&self) -> Result<bool, Error> {
+        if self.is_write_stopped()? {
+            return Ok(true);
+        }
+        Ok(self
+            .property_int_value(properties::ACTUAL_DELAYED_WRITE_RATE)?
+            .is_some_and(|rate| rate != 0))
+    }
+
+    /// RocksDB reports "-1.0" for levels without files.
+    fn parse_property_ratio_value(value: &str) -> Result<Option<f64>, Error> {
+        let ratio = value.parse::<f64>().map_err(|err| {
+            Error::new(format!(
+                "Failed to convert property value {value} to float: {err}"
+            ))
+        })?;
+        Ok((ratio >= 0.0).then_some(ratio))
+    }
+
+    /// Collects the number of files at each level. RocksDB rejects the
+    /// `num-files-at-level<N>` property for levels past the configured number
+    /// of levels, which marks the end.
+    fn level_file_counts<F>(mut files_at_level: F) -> Result<Vec<u64>, Error>
+    where
+        F: FnMut(usize) -> Result<Option<u64>, Error>,
+    {
+        let mut counts = Vec::new();
+        while let Some(n) = files_at_level(counts.len())? {
+            counts.push(n);
+        }
+        Ok(counts)
+    }
+
     /// The sequence number of the most recent transaction.
+    ///
+    /// Every keyed operation written to the database consumes one sequence
+    /// number, so a write batch advances it by the batch's length while reads
+    /// leave it unchanged. Polling it is a cheap way to detect new writes
+    /// before fetching them with
+    /// [`get_updates_since`](Self::get_updates_since)`(previous + 1)`.
     pub fn latest_sequence_number(&self) -> u64 {
         unsafe { ffi::rocksdb_get_latest_sequence_number(self.inner.inner()) }
     }
@@ -2359,6 +2958,171 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
         }
     }
 
+    /// Same as `live_files`, but groups the files by column family name and
+    /// level. Files within a group keep the order reported by RocksDB.
+    pub fn live_files_by_level(&self) -> Result<BTreeMap<(String, i32), Vec<LiveFile>>, Error> {
+        let mut grouped: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for file in self.live_files()? {
+            grouped
+                .entry((file.column_family_name.clone(), file.level))
+                .or_default()
+                .push(file);
+        }
+        Ok(grouped)
+    }
+
+    /// Returns the approximate number of entries and their approximate size
+    /// in bytes held in the memtables of the default column family for keys
+    /// in the range `[from, to)`.
+    pub fn get_approximate_memtable_stats<K: AsRef<[u8]>>(&self, from: K, to: K) -> (u64, u64) {
+        let from = from.as_ref();
+        let to = to.as_ref();
+        let mut count: u64 = 0;
+        let mut size: u64 = 0;
+        unsafe {
+            ffi::rocksdb_approximate_memtable_stats(
+                self.inner.inner(),
+                from.as_ptr() as *const c_char,
+                from.len() as size_t,
+                to.as_ptr() as *const c_char,
+                to.len() as size_t,
+                &mut count,
+                &mut size,
+            );
+        }
+        (count, size)
+    }
+
+    /// Same as `get_approximate_memtable_stats` but only for specific column family
+    pub fn get_approximate_memtable_stats_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        from: K,
+        to: K,
+    ) -> (u64, u64) {
+        let from = from.as_ref();
+        let to = to.as_ref();
+        let mut count: u64 = 0;
+        let mut size: u64 = 0;
+        unsafe {
+            ffi::rocksdb_approximate_memtable_stats_cf(
+                self.inner.inner(),
+                cf.inner(),
+                from.as_ptr() as *const c_char,
+                from.len() as size_t,
+                to.as_ptr() as *const c_char,
+                to.len() as size_t,
+                &mut count,
+                &mut size,
+            );
+        }
+        (count, size)
+    }
+
+    /// Returns the approximate size in bytes of the data in each of the given
+    /// `[start, end)` key ranges of a column family.
+    ///
+    /// `flags` selects whether data in the memtables, in SST files or both is
+    /// counted; see [`SizeApproximationFlags`]. Data that has just been
+    /// written is only accounted for with
+    /// [`SizeApproximationFlags::INCLUDE_MEMTABLES`] until it is flushed.
+    pub fn get_approximate_sizes_cf_opt<K, I>(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        ranges: I,
+        flags: SizeApproximationFlags,
+    ) -> Result<Vec<u64>, Error>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (K, K)>,
+    {
+        let ranges: Vec<(K, K)> = ranges.into_iter().collect();
+        let (start_keys, start_sizes): (Vec<_>, Vec<_>) = ranges
+            .iter()
+            .map(|(start, _)| {
+                (
+                    start.as_ref().as_ptr() as *const c_char,
+                    start.as_ref().len(),
+                )
+            })
+            .unzip();
+        let (end_keys, end_sizes): (Vec<_>, Vec<_>) = ranges
+            .iter()
+            .map(|(_, end)| (end.as_ref().as_ptr() as *const c_char, end.as_ref().len()))
+            .unzip();
+        let mut sizes = vec![0_u64; ranges.len()];
+        unsafe {
+            ffi_try!(ffi::rocksdb_approximate_sizes_cf_with_flags(
+                self.inner.inner(),
+                cf.inner(),
+                ranges.len() as c_int,
+                start_keys.as_ptr(),
+                start_sizes.as_ptr(),
+                end_keys.as_ptr(),
+                end_sizes.as_ptr(),
+                flags.0,
+                sizes.as_mut_ptr(),
+            ));
+        }
+        Ok(sizes)
+    }
+
+    /// Suggests keys splitting the specified column family into shards of
+    /// roughly `target_shard_bytes` each, e.g. to distribute it across
+    /// servers. Each returned key starts a new shard, so `n` keys describe
+    /// `n + 1` shards; a column family smaller than `target_shard_bytes` yields
+    /// no keys.
+    ///
+    /// The split points are chosen among the first and last keys of the live
+    /// SST files, weighed by [`get_approximate_sizes_cf_opt`](Self::get_approximate_sizes_cf_opt),
+    /// so shards are only as even as the file sizes allow and data still in
+    /// the memtables is not accounted for. Keys are compared bytewise, which
+    /// doesn't match column families using a custom comparator.
+    pub fn suggest_split_keys(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        target_shard_bytes: u64,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        if target_shard_bytes == 0 {
+            return Err(Error::new(
+                "The target shard size must be greater than zero".to_owned(),
+            ));
+        }
+
+        let cf_name = column_family_name(cf.inner());
+        let mut boundaries: Vec<Vec<u8>> = self
+            .live_files()?
+            .into_iter()
+            .filter(|file| file.column_family_name == cf_name)
+            .flat_map(|file| [file.start_key, file.end_key])
+            .flatten()
+            .collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+        // Splitting at the last key would leave a shard holding only that key.
+        boundaries.pop();
+        let Some(first) = boundaries.first().cloned() else {
+            return Ok(Vec::new());
+        };
+
+        let sizes = self.get_approximate_sizes_cf_opt(
+            cf,
+            boundaries
+                .iter()
+                .map(|key| (first.as_slice(), key.as_slice())),
+            SizeApproximationFlags::INCLUDE_FILES,
+        )?;
+        let mut split_keys = Vec::new();
+        let mut next_split_size = target_shard_bytes;
+        for (key, size) in boundaries.into_iter().zip(sizes) {
+            if size >= next_split_size {
+                next_split_size = size.saturating_add(target_shard_bytes);
+                split_keys.push(key);
+            }
+        }
+        Ok(split_keys)
+    }
+
     /// Delete sst files whose keys are entirely in the given range.
     ///
     /// Could leave some keys in the range which are in files which are not
@@ -2404,6 +3168,74 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
         }
     }
 
+    /// Reads all data of all column families and validates the block
+    /// checksums of every table file, including blocks no iterator would
+    /// visit.
+    ///
+    /// Returns a `Corruption` error naming the offending file on the first
+    /// checksum mismatch. Consider disabling [`ReadOptions::fill_cache`] so
+    /// that the scan does not evict the block cache.
+    pub fn verify_checksum(&self, readopts: &ReadOptions) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_shim_verify_checksum(
+                self.inner.inner(),
+                readopts.inner
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reads every table and blob file of all column families and compares
+    /// its whole-file checksum with the one recorded in the MANIFEST.
+    ///
+    /// Requires file checksums to be enabled, e.g. with
+    /// [`Options::set_file_checksum_crc32c`], when the files were written;
+    /// returns an `InvalidArgument` error otherwise.
+    pub fn verify_file_checksums(&self, readopts: &ReadOptions) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_shim_verify_file_checksums(
+                self.inner.inner(),
+                readopts.inner
+            ));
+        }
+        Ok(())
+    }
+
+    /// Loads the blocks of every table file of the default column family
+    /// into the block cache by reading all keys, so that the first requests
+    /// after opening the DB are served from memory.
+    ///
+    /// The blocks are only cached if [`ReadOptions::fill_cache`] is enabled,
+    /// which is the default.
+    pub fn warm_up_cache(&self, readopts: &ReadOptions) -> Result<(), Error> {
+        unsafe { Self::scan_all_raw(self.create_iterator(readopts)) }
+    }
+
+    /// Same as `warm_up_cache` but only for specific column family
+    pub fn warm_up_cache_cf(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        readopts: &ReadOptions,
+    ) -> Result<(), Error> {
+        unsafe { Self::scan_all_raw(self.create_iterator_cf(cf.inner(), readopts)) }
+    }
+
+    /// Steps `iter` over all keys, then destroys it and returns its status.
+    unsafe fn scan_all_raw(iter: *mut ffi::rocksdb_iterator_t) -> Result<(), Error> {
+        ffi::rocksdb_iter_seek_to_first(iter);
+        while ffi::rocksdb_iter_valid(iter) != 0 {
+            ffi::rocksdb_iter_next(iter);
+        }
+        let mut err: *mut c_char = ptr::null_mut();
+        ffi::rocksdb_iter_get_error(iter, &mut err);
+        ffi::rocksdb_iter_destroy(iter);
+        if err.is_null() {
+            Ok(())
+        } else {
+            Err(Error::new(crate::ffi_util::error_message(err)))
+        }
+    }
+
     /// Request stopping background work, if wait is true wait until it's done.
     pub fn cancel_all_background_work(&self, wait: bool) {
         unsafe {
@@ -2460,27 +3292,27 @@ impl<T: ThreadMode, D: DBInner> DBCommon<T, D> {
                 &mut ts_lowlen,
             ));
 
-            if ts.is_null() {
-                Err(Error::new("Could not get full_history_ts_low".to_owned()))
-            } else {
-                let mut vec = vec![0; ts_lowlen];
-                ptr::copy_nonoverlapping(ts as *mut u8, vec.as_mut_ptr(), ts_lowlen);
-                ffi::rocksdb_free(ts as *mut c_void);
-                Ok(vec)
-            }
+            CSlice::from_raw(ts, ts_lowlen)
+                .map(|ts| ts.as_ref().to_vec())
+                .ok_or_else(|| Error::new("Could not get full_history_ts_low".to_owned()))
         }
     }
 
     /// Returns the DB identity. This is typically ASCII bytes, but that is not guaranteed.
+    ///
+    /// The identity is assigned when the DB is created. With
+    /// [`Options::set_write_dbid_to_manifest`] enabled (the default) it is
+    /// stored in the MANIFEST, so checkpoints and backups of the DB report the
+    /// same identity as the original.
     pub fn get_db_identity(&self) -> Result<Vec<u8>, Error> {
         unsafe {
             let mut length: usize = 0;
             let identity_ptr = ffi::rocksdb_get_db_identity(self.inner.inner(), &mut length);
-            let identity_vec = raw_data(identity_ptr, length);
-            ffi::rocksdb_free(identity_ptr as *mut c_void);
             // In RocksDB: get_db_identity copies a std::string so it should not fail, but
             // the API allows it to be overridden, so it might
-            identity_vec.ok_or_else(|| Error::new("get_db_identity returned NULL".to_string()))
+            CSlice::from_raw(identity_ptr, length)
+                .map(|identity| identity.as_ref().to_vec())
+                .ok_or_else(|| Error::new("get_db_identity returned NULL".to_string()))
         }
     }
 }
@@ -2570,6 +3402,75 @@ pub struct ColumnFamilyMetaData {
     pub file_count: usize,
 }
 
+/// The outcome of reading a value into a caller provided buffer with
+/// [`DBCommon::get_into_buffer`] and friends.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GetIntoBufferResult {
+    /// The key was not found.
+    NotFound,
+    /// The value was copied to the start of the buffer; holds its size.
+    Found(usize),
+    /// The buffer is too small for the value; holds the size of the value.
+    BufferTooSmall(usize),
+}
+
+/// A snapshot of the properties commonly used to monitor a column family,
+/// as returned by [`DBCommon::cf_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CfStats {
+    /// Number of immutable memtables that have not yet been flushed
+    pub num_immutable_mem_table: u64,
+    /// 1 if a memtable flush is pending, 0 otherwise
+    pub mem_table_flush_pending: u64,
+    /// Number of currently running compactions
+    pub num_running_compactions: u64,
+    /// Estimated number of keys in the memtables and table files
+    pub estimate_num_keys: u64,
+    /// Estimated size of the live data in bytes
+    pub estimate_live_data_size: u64,
+    /// Number of table files at level 0
+    pub level0_file_count: u64,
+}
+
+/// Selects which data [`DBCommon::get_approximate_sizes_cf_opt`] takes into
+/// account. Flags can be combined with `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SizeApproximationFlags(u8);
+
+impl SizeApproximationFlags {
+    /// Neither memtables nor files; rejected by RocksDB as invalid.
+    pub const NONE: Self = Self(0);
+    /// Include data that is still in the memtables.
+    pub const INCLUDE_MEMTABLES: Self = Self(1 << 0);
+    /// Include data in SST files.
+    pub const INCLUDE_FILES: Self = Self(1 << 1);
+
+    /// Returns `true` if all flags set in `other` are also set in `self`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for SizeApproximationFlags {
+    fn default() -> Self {
+        Self::INCLUDE_FILES
+    }
+}
+
+impl std::ops::BitOr for SizeApproximationFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for SizeApproximationFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
 /// The metadata that describes a SST file
 #[derive(Debug, Clone)]
 pub struct LiveFile {
@@ -2607,6 +3508,100 @@ fn convert_options(opts: &[(&str, &str)]) -> Result<Vec<(CString, CString)>, Err
         .collect()
 }
 
+/// Splits an options string like `a=1;b={c=2;d=3};` into its top-level
+/// `(name, value)` pairs.
+fn parse_options_string(options: &str) -> HashMap<String, String> {
+    let mut pairs = HashMap::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in options.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => {
+                if let Some((name, value)) = options[start..i].split_once('=') {
+                    pairs.insert(name.trim().to_owned(), value.trim().to_owned());
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if let Some((name, value)) = options[start..].split_once('=') {
+        pairs.insert(name.trim().to_owned(), value.trim().to_owned());
+    }
+    pairs
+}
+
+fn copy_into_buffer(value: Option<&[u8]>, buffer: &mut [u8]) -> GetIntoBufferResult {
+    match value {
+        None => GetIntoBufferResult::NotFound,
+        Some(value) if value.len() > buffer.len() => {
+            GetIntoBufferResult::BufferTooSmall(value.len())
+        }
+        Some(value) => {
+            buffer[..value.len()].copy_from_slice(value);
+            GetIntoBufferResult::Found(value.len())
+        }
+    }
+}
+
+/// Builds the batch for `put_many` et al, adding every pair of `items` with
+/// `put`.
+///
+/// The batch is reserved up front for the number of pairs `items` reports
+/// in its `size_hint`, assuming they are about as large as the first one.
+fn put_many_batch<I, K, V>(items: I, mut put: impl FnMut(&mut WriteBatch, K, V)) -> WriteBatch
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    // 12 bytes of batch header, and per record at most 16 bytes of type tag,
+    // column family id and length varints.
+    const HEADER_BYTES: usize = 12;
+    const RECORD_OVERHEAD_BYTES: usize = 16;
+
+    let mut items = items.into_iter();
+    let Some((key, value)) = items.next() else {
+        return WriteBatch::default();
+    };
+    let record_bytes = key.as_ref().len() + value.as_ref().len() + RECORD_OVERHEAD_BYTES;
+    let records = items.size_hint().0.saturating_add(1);
+    let mut batch = WriteBatch::with_capacity_bytes(
+        record_bytes
+            .saturating_mul(records)
+            .saturating_add(HEADER_BYTES),
+    );
+    put(&mut batch, key, value);
+    for (key, value) in items {
+        put(&mut batch, key, value);
+    }
+    batch
+}
+
+/// Returns the handle of the default column family of `db`.
+fn default_column_family(db: *mut ffi::rocksdb_t) -> ColumnFamily {
+    ColumnFamily {
+        inner: unsafe { ffi::rocksdb_get_default_column_family_handle(db) },
+    }
+}
+
+fn column_family_name(handle: *mut ffi::rocksdb_column_family_handle_t) -> String {
+    unsafe {
+        let mut name_len: size_t = 0;
+        let name = CSlice::from_raw_parts(
+            ffi::rocksdb_column_family_handle_get_name(handle, &mut name_len),
+            name_len,
+        );
+        String::from_utf8_lossy(name.as_ref()).into_owned()
+    }
+}
+
+/// Converts the per-key outputs of the C multi-get functions: a null error
+/// and a null value mean the key was not found (`Ok(None)`), a non-null error
+/// is reported as `Err` for that key only. Takes ownership of every value and
+/// error pointer and frees them.
 pub(crate) fn convert_values(
     values: Vec<*mut c_char>,
     values_sizes: Vec<usize>,
@@ -2617,12 +3612,11 @@ pub(crate) fn convert_values(
         .zip(values_sizes)
         .zip(errors)
         .map(|((v, s), e)| {
+            // RocksDB leaves the value null on error, but take ownership
+            // regardless so no branch can leak.
+            let value = unsafe { CSlice::from_raw(v, s) };
             if e.is_null() {
-                let value = unsafe { crate::ffi_util::raw_data(v, s) };
-                unsafe {
-                    ffi::rocksdb_free(v as *mut c_void);
-                }
-                Ok(value)
+                Ok(value.map(|value| value.as_ref().to_vec()))
             } else {
                 Err(Error::new(crate::ffi_util::error_message(e)))
             }
@@ -17,7 +17,7 @@ use crate::{
     ffi, Error, ReadOptions, WriteBatch,
 };
 use libc::{c_char, c_uchar, size_t};
-use std::{marker::PhantomData, slice};
+use std::{marker::PhantomData, ops::ControlFlow, slice};
 
 /// A type alias to keep compatibility. See [`DBRawIteratorWithThreadMode`] for details
 pub type DBRawIterator<'a> = DBRawIteratorWithThreadMode<'a, DB>;
@@ -343,6 +343,19 @@ impl<'a, D: DBAccess> DBRawIteratorWithThreadMode<'a, D> {
         }
     }
 
+    /// Returns a slice of the timestamp of the current entry.
+    ///
+    /// Only column families with a timestamp-aware comparator (see
+    /// [`Options::set_comparator_with_ts`](crate::Options::set_comparator_with_ts))
+    /// have timestamps; for other column families the slice is empty.
+    pub fn timestamp(&self) -> Option<&[u8]> {
+        if self.valid() {
+            Some(self.timestamp_impl())
+        } else {
+            None
+        }
+    }
+
     /// Returns pair with slice of the current key and current value.
     pub fn item(&self) -> Option<(&[u8], &[u8])> {
         if self.valid() {
@@ -352,6 +365,55 @@ impl<'a, D: DBAccess> DBRawIteratorWithThreadMode<'a, D> {
         }
     }
 
+    /// Calls `f` with the key and value of every entry from the current
+    /// position onwards, stepping forward until the iterator is exhausted or
+    /// `f` returns [`ControlFlow::Break`].
+    ///
+    /// Keys and values are borrowed directly from the underlying iterator, so
+    /// no allocation happens per entry. Returns the iterator's
+    /// [`status`](DBRawIteratorWithThreadMode::status) once the scan stops.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::ops::ControlFlow;
+    /// use rust_rocksdb::{DB, Options};
+    ///
+    /// let tempdir = tempfile::Builder::new()
+    ///     .prefix("_path_for_rocksdb_storage9")
+    ///     .tempdir()
+    ///     .expect("Failed to create temporary path for the _path_for_rocksdb_storage9.");
+    /// let path = tempdir.path();
+    /// {
+    ///     let db = DB::open_default(path).unwrap();
+    ///     let mut iter = db.raw_iterator();
+    ///
+    ///     // Sum the length of all values
+    ///     let mut total = 0;
+    ///     iter.seek_to_first();
+    ///     iter.scan(|_key, value| {
+    ///         total += value.len();
+    ///         ControlFlow::Continue(())
+    ///     })
+    ///     .unwrap();
+    /// }
+    /// let _ = DB::destroy(&Options::default(), path);
+    /// ```
+    pub fn scan<F>(&mut self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> ControlFlow<()>,
+    {
+        while self.valid() {
+            if f(self.key_impl(), self.value_impl()).is_break() {
+                break;
+            }
+            unsafe {
+                ffi::rocksdb_iter_next(self.inner.as_ptr());
+            }
+        }
+        self.status()
+    }
+
     /// Returns a slice of the current key; assumes the iterator is valid.
     fn key_impl(&self) -> &[u8] {
         // Safety Note: This is safe as all methods that may invalidate the buffer returned
@@ -375,6 +437,21 @@ impl<'a, D: DBAccess> DBRawIteratorWithThreadMode<'a, D> {
             slice::from_raw_parts(val_ptr as *const c_uchar, val_len)
         }
     }
+
+    /// Returns a slice of the current timestamp; assumes the iterator is valid.
+    fn timestamp_impl(&self) -> &[u8] {
+        // Safety Note: This is safe as all methods that may invalidate the buffer returned
+        // take `&mut self`, so borrow checker will prevent use of buffer after seek.
+        unsafe {
+            let mut ts_len: size_t = 0;
+            let ts_ptr = ffi::rocksdb_iter_timestamp(self.inner.as_ptr(), &mut ts_len);
+            if ts_len == 0 {
+                &[]
+            } else {
+                slice::from_raw_parts(ts_ptr as *const c_uchar, ts_len)
+            }
+        }
+    }
 }
 
 impl<D: DBAccess> Drop for DBRawIteratorWithThreadMode<'_, D> {
@@ -523,12 +600,133 @@ impl<D: DBAccess> Iterator for DBIteratorWithThreadMode<'_, D> {
 
 impl<D: DBAccess> std::iter::FusedIterator for DBIteratorWithThreadMode<'_, D> {}
 
+impl<'a, D: DBAccess> DBIteratorWithThreadMode<'a, D> {
+    /// Turns this iterator into one that also yields the timestamp of every
+    /// entry. See [`DBTimestampedIteratorWithThreadMode`] for details.
+    pub fn with_timestamps(self) -> DBTimestampedIteratorWithThreadMode<'a, D> {
+        DBTimestampedIteratorWithThreadMode { inner: self }
+    }
+}
+
 impl<'a, D: DBAccess> Into<DBRawIteratorWithThreadMode<'a, D>> for DBIteratorWithThreadMode<'a, D> {
     fn into(self) -> DBRawIteratorWithThreadMode<'a, D> {
         self.raw
     }
 }
 
+/// A type alias for [`DBTimestampedIteratorWithThreadMode`] over a [`DB`].
+pub type DBTimestampedIterator<'a> = DBTimestampedIteratorWithThreadMode<'a, DB>;
+
+/// An iterator over a column family with user-defined timestamps, yielding
+/// `(key, value, timestamp)` triples. Created by
+/// [`DBIteratorWithThreadMode::with_timestamps`].
+///
+/// Which version of each key is visible is controlled by
+/// [`ReadOptions::set_timestamp`](crate::ReadOptions::set_timestamp), which
+/// RocksDB requires when iterating over a column family with timestamps.
+/// For column families without timestamps the timestamp is empty.
+pub struct DBTimestampedIteratorWithThreadMode<'a, D: DBAccess> {
+    inner: DBIteratorWithThreadMode<'a, D>,
+}
+
+impl<D: DBAccess> DBTimestampedIteratorWithThreadMode<'_, D> {
+    /// Repositions the iterator, as
+    /// [`DBIteratorWithThreadMode::set_mode`] does.
+    pub fn set_mode(&mut self, mode: IteratorMode) {
+        self.inner.set_mode(mode);
+    }
+}
+
+impl<D: DBAccess> Iterator for DBTimestampedIteratorWithThreadMode<'_, D> {
+    type Item = Result<(Box<[u8]>, Box<[u8]>, Box<[u8]>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let it = &mut self.inner;
+        if it.done {
+            None
+        } else if let Some((key, value)) = it.raw.item() {
+            let item = (
+                Box::from(key),
+                Box::from(value),
+                Box::from(it.raw.timestamp_impl()),
+            );
+            match it.direction {
+                Direction::Forward => it.raw.next(),
+                Direction::Reverse => it.raw.prev(),
+            }
+            Some(Ok(item))
+        } else {
+            it.done = true;
+            it.raw.status().err().map(Result::Err)
+        }
+    }
+}
+
+impl<D: DBAccess> std::iter::FusedIterator for DBTimestampedIteratorWithThreadMode<'_, D> {}
+
+/// A type alias for [`DBKeyIteratorWithThreadMode`] over a [`DB`].
+pub type DBKeyIterator<'a> = DBKeyIteratorWithThreadMode<'a, DB>;
+
+/// An iterator over a database or column family that yields only keys.
+///
+/// Values are never copied out of RocksDB, which saves an allocation per
+/// entry when they are large. RocksDB still reads the data blocks holding the
+/// values, and values stored in blob files are still fetched.
+///
+/// ```
+/// use rust_rocksdb::{DB, IteratorMode, Options};
+///
+/// let tempdir = tempfile::Builder::new()
+///     .prefix("_path_for_rocksdb_storage_key_iterator")
+///     .tempdir()
+///     .expect("Failed to create temporary path for the _path_for_rocksdb_storage_key_iterator.");
+/// let path = tempdir.path();
+/// {
+///     let db = DB::open_default(path).unwrap();
+///     db.put(b"my key", b"my value").unwrap();
+///     for key in db.key_iterator(IteratorMode::Start) {
+///         println!("Saw {:?}", key.unwrap());
+///     }
+/// }
+/// let _ = DB::destroy(&Options::default(), path);
+/// ```
+pub struct DBKeyIteratorWithThreadMode<'a, D: DBAccess> {
+    inner: DBIteratorWithThreadMode<'a, D>,
+}
+
+impl<'a, D: DBAccess> DBKeyIteratorWithThreadMode<'a, D> {
+    pub(crate) fn new(inner: DBIteratorWithThreadMode<'a, D>) -> Self {
+        Self { inner }
+    }
+
+    pub fn set_mode(&mut self, mode: IteratorMode) {
+        self.inner.set_mode(mode);
+    }
+}
+
+impl<D: DBAccess> Iterator for DBKeyIteratorWithThreadMode<'_, D> {
+    type Item = Result<Box<[u8]>, Error>;
+
+    fn next(&mut self) -> Option<Result<Box<[u8]>, Error>> {
+        let it = &mut self.inner;
+        if it.done {
+            None
+        } else if let Some(key) = it.raw.key() {
+            let key = Box::from(key);
+            match it.direction {
+                Direction::Forward => it.raw.next(),
+                Direction::Reverse => it.raw.prev(),
+            }
+            Some(Ok(key))
+        } else {
+            it.done = true;
+            it.raw.status().err().map(Result::Err)
+        }
+    }
+}
+
+impl<D: DBAccess> std::iter::FusedIterator for DBKeyIteratorWithThreadMode<'_, D> {}
+
 /// Iterates the batches of writes since a given sequence number.
 ///
 /// `DBWALIterator` is returned by `DB::get_updates_since()` and will return the
@@ -27,7 +27,8 @@ use crate::{
     compaction_filter::{self, CompactionFilterCallback, CompactionFilterFn},
     compaction_filter_factory::{self, CompactionFilterFactory},
     comparator::{
-        ComparatorCallback, ComparatorWithTsCallback, CompareFn, CompareTsFn, CompareWithoutTsFn,
+        u64_ts, ComparatorCallback, ComparatorWithTsCallback, CompareFn, CompareTsFn,
+        CompareWithoutTsFn,
     },
     db::DBAccess,
     env::Env,
@@ -158,16 +159,35 @@ pub struct Cache(pub(crate) Arc<CacheWrapper>);
 
 impl Cache {
     /// Creates an LRU cache with capacity in bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if RocksDB fails to allocate the cache. See
+    /// [`Cache::try_new_lru_cache`] for a non-panicking alternative.
     pub fn new_lru_cache(capacity: size_t) -> Cache {
-        let inner = NonNull::new(unsafe { ffi::rocksdb_cache_create_lru(capacity) }).unwrap();
-        Cache(Arc::new(CacheWrapper { inner }))
+        Self::try_new_lru_cache(capacity).unwrap()
+    }
+
+    /// Same as [`Cache::new_lru_cache`] but returns an error instead of
+    /// panicking if the cache could not be created.
+    pub fn try_new_lru_cache(capacity: size_t) -> Result<Cache, Error> {
+        Self::from_raw(unsafe { ffi::rocksdb_cache_create_lru(capacity) })
     }
 
     /// Creates an LRU cache with custom options.
+    ///
+    /// # Panics
+    ///
+    /// Panics if RocksDB fails to allocate the cache. See
+    /// [`Cache::try_new_lru_cache_opts`] for a non-panicking alternative.
     pub fn new_lru_cache_opts(opts: &LruCacheOptions) -> Cache {
-        let inner =
-            NonNull::new(unsafe { ffi::rocksdb_cache_create_lru_opts(opts.inner) }).unwrap();
-        Cache(Arc::new(CacheWrapper { inner }))
+        Self::try_new_lru_cache_opts(opts).unwrap()
+    }
+
+    /// Same as [`Cache::new_lru_cache_opts`] but returns an error instead of
+    /// panicking if the cache could not be created.
+    pub fn try_new_lru_cache_opts(opts: &LruCacheOptions) -> Result<Cache, Error> {
+        Self::from_raw(unsafe { ffi::rocksdb_cache_create_lru_opts(opts.inner) })
     }
 
     /// Creates a HyperClockCache with capacity in bytes.
@@ -190,13 +210,31 @@ impl Cache {
     /// The latter is generally preferable, and picking the larger of
     /// block size and meta data block size is a reasonable choice that
     /// errs towards this side.
+    ///
+    /// # Panics
+    ///
+    /// Panics if RocksDB fails to allocate the cache. See
+    /// [`Cache::try_new_hyper_clock_cache`] for a non-panicking alternative.
     pub fn new_hyper_clock_cache(capacity: size_t, estimated_entry_charge: size_t) -> Cache {
-        Cache(Arc::new(CacheWrapper {
-            inner: NonNull::new(unsafe {
-                ffi::rocksdb_cache_create_hyper_clock(capacity, estimated_entry_charge)
-            })
-            .unwrap(),
-        }))
+        Self::try_new_hyper_clock_cache(capacity, estimated_entry_charge).unwrap()
+    }
+
+    /// Same as [`Cache::new_hyper_clock_cache`] but returns an error instead
+    /// of panicking if the cache could not be created.
+    pub fn try_new_hyper_clock_cache(
+        capacity: size_t,
+        estimated_entry_charge: size_t,
+    ) -> Result<Cache, Error> {
+        Self::from_raw(unsafe {
+            ffi::rocksdb_cache_create_hyper_clock(capacity, estimated_entry_charge)
+        })
+    }
+
+    fn from_raw(inner: *mut ffi::rocksdb_cache_t) -> Result<Cache, Error> {
+        match NonNull::new(inner) {
+            Some(inner) => Ok(Cache(Arc::new(CacheWrapper { inner }))),
+            None => Err(Error::new("Could not create RocksDB cache".to_owned())),
+        }
     }
 
     /// Returns the cache memory usage in bytes.
@@ -217,9 +255,56 @@ impl Cache {
     }
 }
 
+pub(crate) struct ComparatorWrapper {
+    pub(crate) inner: NonNull<ffi::rocksdb_comparator_t>,
+}
+
+impl Drop for ComparatorWrapper {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_comparator_destroy(self.inner.as_ptr());
+        }
+    }
+}
+
+/// A comparator that can be shared by several column families.
+///
+/// Unlike [`Options::set_comparator`], which creates a new comparator for
+/// every call, a `Comparator` is created once and passed to
+/// [`Options::set_comparator_handle`] for each column family that uses it.
+/// Options and databases using it keep it alive.
+#[derive(Clone)]
+pub struct Comparator(pub(crate) Arc<ComparatorWrapper>);
+
+impl Comparator {
+    /// Creates a comparator ordering keys by `compare_fn`.
+    ///
+    /// The client must ensure that the comparator has the same name and
+    /// orders keys *exactly* the same as the comparator provided to previous
+    /// open calls on the same DB.
+    pub fn new(name: impl CStrLike, compare_fn: Box<CompareFn>) -> Self {
+        let cb = Box::new(ComparatorCallback {
+            name: name.into_c_string().unwrap(),
+            compare_fn,
+        });
+
+        let inner = NonNull::new(unsafe {
+            ffi::rocksdb_comparator_create(
+                Box::into_raw(cb).cast::<c_void>(),
+                Some(ComparatorCallback::destructor_callback),
+                Some(ComparatorCallback::compare_callback),
+                Some(ComparatorCallback::name_callback),
+            )
+        })
+        .unwrap();
+        Comparator(Arc::new(ComparatorWrapper { inner }))
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct OptionsMustOutliveDB {
     env: Option<Env>,
+    comparator: Option<Comparator>,
     row_cache: Option<Cache>,
     blob_cache: Option<Cache>,
     block_based: Option<BlockBasedOptionsMustOutliveDB>,
@@ -230,6 +315,7 @@ impl OptionsMustOutliveDB {
     pub(crate) fn clone(&self) -> Self {
         Self {
             env: self.env.clone(),
+            comparator: self.comparator.clone(),
             row_cache: self.row_cache.clone(),
             blob_cache: self.blob_cache.clone(),
             block_based: self
@@ -429,6 +515,7 @@ unsafe impl Send for IngestExternalFileOptions {}
 unsafe impl Send for CacheWrapper {}
 unsafe impl Send for CompactOptions {}
 unsafe impl Send for WriteBufferManagerWrapper {}
+unsafe impl Send for ComparatorWrapper {}
 
 // Sync is similarly safe for many types because they do not expose interior mutability, and their
 // use within the rocksdb library is generally behind a const reference
@@ -443,6 +530,7 @@ unsafe impl Sync for IngestExternalFileOptions {}
 unsafe impl Sync for CacheWrapper {}
 unsafe impl Sync for CompactOptions {}
 unsafe impl Sync for WriteBufferManagerWrapper {}
+unsafe impl Sync for ComparatorWrapper {}
 
 impl Drop for Options {
     fn drop(&mut self) {
@@ -1117,6 +1205,17 @@ impl Options {
         }
     }
 
+    /// Sets a single option by name through the options string parser.
+    ///
+    /// This is for options that have no `rocksdb_options_set_*` function in
+    /// the RocksDB C API. The typed setters that call it only pass values
+    /// that always parse, so a parse failure is a bug and panics.
+    fn set_option_via_string(&mut self, name: &str, value: impl std::fmt::Display) {
+        if let Err(err) = self.set_options_from_string(format!("{name}={value}").as_str()) {
+            panic!("{name}={value} should be a valid option string: {err}");
+        }
+    }
+
     /// By default, RocksDB uses only one background thread for flush and
     /// compaction. Calling this function will set it up such that total of
     /// `total_threads` is used. Good value for `total_threads` is the number of
@@ -1250,13 +1349,27 @@ impl Options {
     /// the database will switch to read-only mode and fail all other
     /// Write operations.
     ///
-    /// Default: false
+    /// Default: true
     pub fn set_paranoid_checks(&mut self, enabled: bool) {
         unsafe {
             ffi::rocksdb_options_set_paranoid_checks(self.inner, c_uchar::from(enabled));
         }
     }
 
+    /// Enable/disable computing a crc32c checksum of every table and blob
+    /// file written, which is recorded in the MANIFEST.
+    ///
+    /// The checksums are checked by [`DB::verify_file_checksums`].
+    ///
+    /// Default: false
+    ///
+    /// [`DB::verify_file_checksums`]: crate::DBCommon::verify_file_checksums
+    pub fn set_file_checksum_crc32c(&mut self, enabled: bool) {
+        unsafe {
+            ffi::rocksdb_shim_options_set_file_checksum_crc32c(self.inner, c_uchar::from(enabled));
+        }
+    }
+
     /// A list of paths where SST files can be put into, with its target size.
     /// Newer data is placed into paths specified earlier in the vector while
     /// older data gradually moves to paths specified later in the vector.
@@ -1652,6 +1765,55 @@ impl Options {
         }
     }
 
+    /// Returns the value of the `ttl` option, `0xfffffffffffffffe` if it was
+    /// left for RocksDB to pick.
+    pub fn get_ttl(&self) -> u64 {
+        unsafe { ffi::rocksdb_options_get_ttl(self.inner) }
+    }
+
+    /// Returns the value of the `periodic_compaction_seconds` option,
+    /// `0xfffffffffffffffe` if it was left for RocksDB to pick.
+    pub fn get_periodic_compaction_seconds(&self) -> u64 {
+        unsafe { ffi::rocksdb_options_get_periodic_compaction_seconds(self.inner) }
+    }
+
+    /// Returns the periodic compaction interval resulting from the `ttl` and
+    /// `periodic_compaction_seconds` options, following the rule described on
+    /// [`set_periodic_compaction_seconds`](Self::set_periodic_compaction_seconds):
+    ///
+    /// - an option left at its default counts as 30 days,
+    /// - if both options are zero, periodic compaction is off and `None` is
+    ///   returned,
+    /// - otherwise the smaller non-zero value is picked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_ttl(3600);
+    /// opts.set_periodic_compaction_seconds(0);
+    /// assert_eq!(opts.effective_periodic_compaction_seconds(), Some(3600));
+    /// ```
+    pub fn effective_periodic_compaction_seconds(&self) -> Option<u64> {
+        const PICK_DEFAULT: u64 = u64::MAX - 1;
+        const DEFAULT_SECS: u64 = 30 * 24 * 60 * 60;
+        let effective = |secs| {
+            if secs == PICK_DEFAULT {
+                DEFAULT_SECS
+            } else {
+                secs
+            }
+        };
+
+        [self.get_ttl(), self.get_periodic_compaction_seconds()]
+            .into_iter()
+            .map(effective)
+            .filter(|&secs| secs != 0)
+            .min()
+    }
+
     pub fn set_merge_operator_associative<F: MergeFn + Clone>(
         &mut self,
         name: impl CStrLike,
@@ -1719,6 +1881,10 @@ impl Options {
     ///
     /// If multi-threaded compaction is used, `filter_fn` may be called multiple times
     /// simultaneously.
+    ///
+    /// When blob files are enabled (see [`Options::set_enable_blob_files`]), values
+    /// stored in blob files are read back before `filter_fn` is invoked, so the
+    /// filter always sees the actual value rather than the blob reference.
     pub fn set_compaction_filter<F>(&mut self, name: impl CStrLike, filter_fn: F)
     where
         F: CompactionFilterFn + Send + 'static,
@@ -1788,6 +1954,32 @@ impl Options {
         }
     }
 
+    /// Sets a comparator created with [`Comparator::new`], used to define the
+    /// order of keys in the table.
+    ///
+    /// The same comparator can be set on the options of several column
+    /// families. Databases opened with, or column families created from, these
+    /// options keep it alive, so the options and the `Comparator` can be
+    /// dropped afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::{Comparator, Options};
+    ///
+    /// let comparator = Comparator::new("reverse", Box::new(|a: &[u8], b: &[u8]| b.cmp(a)));
+    /// let mut cf1_opts = Options::default();
+    /// cf1_opts.set_comparator_handle(&comparator);
+    /// let mut cf2_opts = Options::default();
+    /// cf2_opts.set_comparator_handle(&comparator);
+    /// ```
+    pub fn set_comparator_handle(&mut self, comparator: &Comparator) {
+        unsafe {
+            ffi::rocksdb_options_set_comparator(self.inner, comparator.0.inner.as_ptr());
+        }
+        self.outlive.comparator = Some(comparator.clone());
+    }
+
     /// Sets the comparator that are timestamp-aware, used to define the order of keys in the table,
     /// taking timestamp into consideration.
     /// Find more information on timestamp-aware comparator on [here](https://github.com/facebook/rocksdb/wiki/User-defined-Timestamp)
@@ -1824,15 +2016,104 @@ impl Options {
         }
     }
 
+    /// Sets a timestamp-aware comparator for `u64` timestamps in little endian
+    /// encoding, ordering keys exactly like RocksDB's built-in
+    /// `BytewiseComparatorWithU64Ts`: bytewise by key, then newest timestamp
+    /// first. It uses the built-in comparator's name, so a column family
+    /// created by another RocksDB binding with that comparator can be opened.
+    ///
+    /// Timestamps passed to `*_with_ts` writes and to
+    /// [`ReadOptions::set_timestamp`] must be 8 bytes, e.g. `ts.to_le_bytes()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_u64_timestamp_comparator();
+    /// ```
+    pub fn set_u64_timestamp_comparator(&mut self) {
+        self.set_comparator_with_ts(
+            u64_ts::NAME,
+            u64_ts::TIMESTAMP_SIZE,
+            Box::new(u64_ts::compare),
+            Box::new(u64_ts::compare_ts),
+            Box::new(u64_ts::compare_without_ts),
+        );
+    }
+
     pub fn set_prefix_extractor(&mut self, prefix_extractor: SliceTransform) {
         unsafe {
             ffi::rocksdb_options_set_prefix_extractor(self.inner, prefix_extractor.inner);
         }
     }
 
-    // Use this if you don't need to keep the data sorted, i.e. you'll never use
-    // an iterator, only Put() and Get() API calls
-    //
+    /// Sets the prefix extractor used to remember the last memtable insert
+    /// position of each prefix. Inserts of keys that follow the previous key of
+    /// their prefix, such as time-ordered keys within a prefix, then skip most
+    /// of the memtable search.
+    ///
+    /// Only the skip list memtable supports insert hints, and they are ignored
+    /// for concurrent memtable writes.
+    ///
+    /// The option is set through the options string parser, so only
+    /// transforms that can be described in an options string are supported,
+    /// i.e. those created by [`SliceTransform::create_fixed_prefix`] and
+    /// [`SliceTransform::create_noop`]. The transform is only read, so it can
+    /// still be passed to e.g. [`set_prefix_extractor`](Self::set_prefix_extractor).
+    ///
+    /// Default: none
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the options unchanged, for transforms
+    /// created by [`SliceTransform::create`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::{Options, SliceTransform};
+    ///
+    /// let mut opts = Options::default();
+    /// let prefix_extractor = SliceTransform::create_fixed_prefix(4);
+    /// opts.set_memtable_insert_with_hint_prefix_extractor(&prefix_extractor)
+    ///     .unwrap();
+    /// opts.set_prefix_extractor(prefix_extractor);
+    /// ```
+    pub fn set_memtable_insert_with_hint_prefix_extractor(
+        &mut self,
+        prefix_extractor: &SliceTransform,
+    ) -> Result<(), Error> {
+        let value = prefix_extractor.options_string_value().ok_or_else(|| {
+            Error::new(
+                "Custom slice transforms can't be used as memtable insert hint prefix extractor"
+                    .to_owned(),
+            )
+        })?;
+        self.set_options_from_string(
+            format!("memtable_insert_with_hint_prefix_extractor={value}").as_str(),
+        )?;
+        Ok(())
+    }
+
+    /// Use this if you don't need to keep the data sorted, i.e. you'll never use
+    /// an iterator, only Put() and Get() API calls.
+    ///
+    /// Internally, it installs a block-based table factory with a
+    /// `block_cache_size_mb` MB LRU block cache, a bloom filter and a data
+    /// block hash index, and enables whole key filtering in the memtable. A
+    /// later call to `set_block_based_table_factory` replaces these table
+    /// settings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.optimize_for_point_lookup(64);
+    /// ```
     pub fn optimize_for_point_lookup(&mut self, block_cache_size_mb: u64) {
         unsafe {
             ffi::rocksdb_options_optimize_for_point_lookup(self.inner, block_cache_size_mb);
@@ -1841,6 +2122,12 @@ impl Options {
 
     /// Sets the optimize_filters_for_hits flag
     ///
+    /// When enabled, filters are not built for the bottommost level, which
+    /// holds most of the data. Use this when lookups almost always find their
+    /// key: it saves the memory and space of the largest filters at the cost of
+    /// an extra data block read for misses. Lookups of present keys are
+    /// unaffected.
+    ///
     /// Default: `false`
     ///
     /// # Examples
@@ -2070,6 +2357,44 @@ impl Options {
         }
     }
 
+    /// The maximum number of microseconds that a write operation will use a yielding spin loop
+    /// to coordinate with other write threads before blocking on a mutex, when
+    /// [`set_enable_write_thread_adaptive_yield`](Self::set_enable_write_thread_adaptive_yield)
+    /// is enabled.
+    ///
+    /// Default: 100
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_write_thread_max_yield_usec(200);
+    /// ```
+    pub fn set_write_thread_max_yield_usec(&mut self, micros: u64) {
+        self.set_option_via_string("write_thread_max_yield_usec", micros);
+    }
+
+    /// The latency in microseconds after which a `std::this_thread::yield` call
+    /// during the adaptive yield spin loop is considered slow. If enough slow
+    /// yields happen, the write thread stops spinning and blocks on a mutex
+    /// instead, since yielding is then likely to be running other threads.
+    ///
+    /// Default: 3
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_write_thread_slow_yield_usec(5);
+    /// ```
+    pub fn set_write_thread_slow_yield_usec(&mut self, micros: u64) {
+        self.set_option_via_string("write_thread_slow_yield_usec", micros);
+    }
+
     /// Specifies whether an iteration->Next() sequentially skips over keys with the same user-key or not.
     ///
     /// This number specifies the number of keys (with the same userkey)
@@ -2548,11 +2873,50 @@ impl Options {
         }
     }
 
+    /// If enabled, writes use two queues: one for writes that skip the
+    /// memtable and one for those that also write to the memtable. This lets
+    /// the WAL-only writes of transactions being prepared proceed without
+    /// waiting on memtable inserts, which mainly benefits TransactionDB with
+    /// the WRITE_PREPARED or WRITE_UNPREPARED write policy. Combined with
+    /// [`set_unordered_write`](Self::set_unordered_write) and such a
+    /// TransactionDB, it keeps snapshots immutable.
+    ///
+    /// It can't be combined with
+    /// [`set_enable_pipelined_write`](Self::set_enable_pipelined_write).
+    ///
+    /// Default: false
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_two_write_queues(true);
+    /// ```
+    pub fn set_two_write_queues(&mut self, enabled: bool) {
+        self.set_option_via_string("two_write_queues", enabled);
+    }
+
     /// Sets maximum number of threads that will
     /// concurrently perform a compaction job by breaking it into multiple,
     /// smaller ones that are run simultaneously.
     ///
+    /// Subcompactions are drawn from the background job pool, so
+    /// [`set_max_background_jobs`](Options::set_max_background_jobs) should
+    /// leave room for them. Large manual compactions via `compact_range`
+    /// benefit the most.
+    ///
     /// Default: 1 (i.e. no subcompactions)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_max_subcompactions(4);
+    /// ```
     pub fn set_max_subcompactions(&mut self, num: u32) {
         unsafe {
             ffi::rocksdb_options_set_max_subcompactions(self.inner, num);
@@ -2571,6 +2935,11 @@ impl Options {
         }
     }
 
+    /// Returns the value of the `max_background_jobs` option.
+    pub fn get_max_background_jobs(&self) -> c_int {
+        unsafe { ffi::rocksdb_options_get_max_background_jobs(self.inner) }
+    }
+
     /// Sets the maximum number of concurrent background compaction jobs, submitted to
     /// the default LOW priority thread pool.
     /// We first try to schedule compactions based on
@@ -2582,7 +2951,12 @@ impl Options {
     /// LOW priority thread pool. For more information, see
     /// Env::SetBackgroundThreads
     ///
-    /// Default: `1`
+    /// This is a legacy knob: [`set_max_background_jobs`](Options::set_max_background_jobs)
+    /// supersedes it. RocksDB only honors the split between compactions and
+    /// flushes when either this or `max_background_flushes` is set explicitly;
+    /// otherwise it derives both limits from `max_background_jobs`.
+    ///
+    /// Default: `-1` (derived from `max_background_jobs`)
     ///
     /// # Examples
     ///
@@ -2603,6 +2977,11 @@ impl Options {
         }
     }
 
+    /// Returns the value of the `max_background_compactions` option.
+    pub fn get_max_background_compactions(&self) -> c_int {
+        unsafe { ffi::rocksdb_options_get_max_background_compactions(self.inner) }
+    }
+
     /// Sets the maximum number of concurrent background memtable flush jobs, submitted to
     /// the HIGH priority thread pool.
     ///
@@ -2618,7 +2997,10 @@ impl Options {
     /// HIGH priority thread pool. For more information, see
     /// Env::SetBackgroundThreads
     ///
-    /// Default: `1`
+    /// Like `max_background_compactions`, this is superseded by
+    /// [`set_max_background_jobs`](Options::set_max_background_jobs).
+    ///
+    /// Default: `-1` (derived from `max_background_jobs`)
     ///
     /// # Examples
     ///
@@ -2639,6 +3021,11 @@ impl Options {
         }
     }
 
+    /// Returns the value of the `max_background_flushes` option.
+    pub fn get_max_background_flushes(&self) -> c_int {
+        unsafe { ffi::rocksdb_options_get_max_background_flushes(self.inner) }
+    }
+
     /// Disables automatic compactions. Manual compactions can still
     /// be issued on this column family
     ///
@@ -2716,6 +3103,9 @@ impl Options {
     /// * new sizeof(new_value) <= sizeof(old_value)
     /// * old_value for that key is a put i.e. kTypeValue
     ///
+    /// See [`set_inplace_update_callback`](Self::set_inplace_update_callback)
+    /// to combine the new value with the existing one instead.
+    ///
     /// Default: false.
     pub fn set_inplace_update_support(&mut self, enabled: bool) {
         unsafe {
@@ -2732,6 +3122,112 @@ impl Options {
         }
     }
 
+    /// Sets the callback that turns a `put` into a read-modify-write of the
+    /// existing value, e.g. to add to a counter without reading it first.
+    /// Requires [`set_inplace_update_support`](Self::set_inplace_update_support).
+    ///
+    /// The callback receives the current value of the key, or `None` if there
+    /// is none, and the value that was put, and decides what to store; see
+    /// [`InplaceUpdateResult`]. Only the value that was put is written to the
+    /// WAL, so the callback must behave the same when the DB is reopened.
+    ///
+    /// RocksDB only takes a plain function pointer, so the callback can't
+    /// capture anything: it must be a function or a closure without captures.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `callback` is not zero-sized, i.e. captures something.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::{InplaceUpdateResult, Options};
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_inplace_update_support(true);
+    /// opts.set_inplace_update_callback(|existing: Option<&mut [u8]>, delta: &[u8]| {
+    ///     match existing {
+    ///         Some(existing) if existing.len() == 8 && delta.len() == 8 => {
+    ///             let sum = u64::from_le_bytes(existing[..].try_into().unwrap())
+    ///                 + u64::from_le_bytes(delta.try_into().unwrap());
+    ///             existing.copy_from_slice(&sum.to_le_bytes());
+    ///             InplaceUpdateResult::UpdatedInplace(8)
+    ///         }
+    ///         _ => InplaceUpdateResult::Updated(delta.to_vec()),
+    ///     }
+    /// });
+    /// ```
+    pub fn set_inplace_update_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(Option<&mut [u8]>, &[u8]) -> InplaceUpdateResult + Copy + Send + Sync + 'static,
+    {
+        assert_eq!(
+            std::mem::size_of::<F>(),
+            0,
+            "in-place update callbacks can't capture anything"
+        );
+        // Nothing to keep: the callback is conjured from its type.
+        let _ = callback;
+        unsafe {
+            ffi::rocksdb_shim_options_set_inplace_callback(
+                self.inner,
+                Some(Self::inplace_callback::<F>),
+            );
+        }
+    }
+
+    extern "C" fn inplace_callback<F>(
+        existing_value: *mut c_char,
+        existing_value_size: *mut u32,
+        delta_value: ffi::rocksdb_shim_slice_t,
+        merged_value: *mut c_void,
+    ) -> c_int
+    where
+        F: Fn(Option<&mut [u8]>, &[u8]) -> InplaceUpdateResult + Copy + Send + Sync + 'static,
+    {
+        // `UpdateStatus` of RocksDB
+        const UPDATE_FAILED: c_int = 0;
+        const UPDATED_INPLACE: c_int = 1;
+        const UPDATED: c_int = 2;
+
+        // F is zero-sized and a value of it was handed to
+        // set_inplace_update_callback, so any value of it is that callback.
+        let callback: F = unsafe { std::mem::zeroed() };
+        let existing_len = if existing_value.is_null() {
+            None
+        } else {
+            Some(unsafe { *existing_value_size } as usize)
+        };
+        let existing = existing_len
+            .map(|len| unsafe { slice::from_raw_parts_mut(existing_value.cast::<u8>(), len) });
+        let delta =
+            unsafe { slice::from_raw_parts(delta_value.data.cast::<u8>(), delta_value.size) };
+        let result = match catch_unwind(AssertUnwindSafe(|| callback(existing, delta))) {
+            Ok(result) => result,
+            Err(_) => std::process::abort(),
+        };
+        match result {
+            InplaceUpdateResult::Failed => UPDATE_FAILED,
+            InplaceUpdateResult::UpdatedInplace(len) => match existing_len {
+                Some(existing_len) if len <= existing_len => {
+                    unsafe { *existing_value_size = len as u32 };
+                    UPDATED_INPLACE
+                }
+                _ => UPDATE_FAILED,
+            },
+            InplaceUpdateResult::Updated(value) => {
+                unsafe {
+                    ffi::rocksdb_shim_string_assign(
+                        merged_value,
+                        value.as_ptr().cast::<c_char>(),
+                        value.len(),
+                    );
+                }
+                UPDATED
+            }
+        }
+    }
+
     /// Different max-size multipliers for different levels.
     /// These are multiplied by max_bytes_for_level_multiplier to arrive
     /// at the max-size of each level.
@@ -3065,7 +3561,10 @@ impl Options {
         }
     }
 
-    /// If not zero, dump rocksdb.stats to RocksDB to LOG every `stats_persist_period_sec`.
+    /// If not zero, take a snapshot of the statistics every
+    /// `stats_persist_period_sec` and keep it in the in-memory stats history.
+    ///
+    /// This needs statistics to be enabled, see [`Options::enable_statistics`].
     ///
     /// Default: `600` (10 mins)
     ///
@@ -3184,6 +3683,8 @@ impl Options {
     /// 4. If both are not 0, WAL files will be checked every 10 min and both
     ///    checks will be performed with ttl being first.
     ///
+    /// Archived logs remain readable through `DB::get_updates_since`.
+    ///
     /// Default: 0
     pub fn set_wal_ttl_seconds(&mut self, secs: u64) {
         unsafe {
@@ -3245,6 +3746,11 @@ impl Options {
 
     /// Allow the OS to mmap file for writing.
     ///
+    /// Memory mapped writes and direct I/O writes are mutually exclusive:
+    /// opening a database with both this and
+    /// [`set_use_direct_io_for_flush_and_compaction`](Options::set_use_direct_io_for_flush_and_compaction)
+    /// enabled fails with a `NotSupported` error.
+    ///
     /// Default: false
     ///
     /// # Examples
@@ -3263,6 +3769,13 @@ impl Options {
 
     /// Allow the OS to mmap file for reading sst tables.
     ///
+    /// This avoids a `pread` system call per block read, which mostly pays off
+    /// for read-heavy workloads on fast storage whose data fits in the page
+    /// cache. Memory mapped reads and direct I/O reads are mutually exclusive:
+    /// opening a database with both this and
+    /// [`set_use_direct_reads`](Options::set_use_direct_reads) enabled fails
+    /// with a `NotSupported` error.
+    ///
     /// Default: false
     ///
     /// # Examples
@@ -3688,6 +4201,9 @@ impl Options {
 
     /// Sets the blob cache.
     ///
+    /// Blob values are not stored in the block cache, so without a blob cache
+    /// every read of a blob value goes to the blob file.
+    ///
     /// Using a dedicated object for blobs and using the same object for the block and blob caches
     /// are both supported. In the latter case, note that blobs are less valuable from a caching
     /// perspective than SST blocks, and some cache implementations have configuration options that
@@ -3702,6 +4218,33 @@ impl Options {
         self.outlive.blob_cache = Some(cache.clone());
     }
 
+    /// Sets whether newly written blob files are inserted into the blob cache.
+    /// Prepopulating the cache on flush makes recently written blobs, which
+    /// are often the hottest, readable without a blob file read.
+    /// Requires a blob cache, see [`set_blob_cache`](Options::set_blob_cache).
+    ///
+    /// Default: `PrepopulateBlobCache::Disable`
+    ///
+    /// Dynamically changeable through SetOptions() API
+    pub fn set_prepopulate_blob_cache(&mut self, val: PrepopulateBlobCache) {
+        unsafe {
+            ffi::rocksdb_options_set_prepopulate_blob_cache(self.inner, val as c_int);
+        }
+    }
+
+    /// Sets the LSM tree level at which blob files start being written.
+    /// Values written to lower levels stay inline in the SST files, which
+    /// keeps short-lived data out of blob files.
+    ///
+    /// Default: 0
+    ///
+    /// Dynamically changeable through SetOptions() API
+    pub fn set_blob_file_starting_level(&mut self, val: c_int) {
+        unsafe {
+            ffi::rocksdb_options_set_blob_file_starting_level(self.inner, val);
+        }
+    }
+
     /// Set this option to true during creation of database if you want
     /// to be able to ingest behind (call IngestExternalFile() skipping keys
     /// that already exist, rather than overwriting matching keys).
@@ -3747,6 +4290,34 @@ impl Options {
         }
     }
 
+    /// Like [`add_compact_on_deletion_collector_factory`](Options::add_compact_on_deletion_collector_factory),
+    /// but only triggers on the sliding window: an SST file is marked as
+    /// need-compaction when at least `num_dels_trigger` deletion entries are
+    /// observed in any `window_size` consecutive entries. The deletion ratio
+    /// trigger is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.add_compact_on_deletion_collector_factory_simple(128, 64);
+    /// ```
+    pub fn add_compact_on_deletion_collector_factory_simple(
+        &mut self,
+        window_size: size_t,
+        num_dels_trigger: size_t,
+    ) {
+        unsafe {
+            ffi::rocksdb_options_add_compact_on_deletion_collector_factory(
+                self.inner,
+                window_size,
+                num_dels_trigger,
+            );
+        }
+    }
+
     /// <https://github.com/facebook/rocksdb/wiki/Write-Buffer-Manager>
     /// Write buffer manager helps users control the total memory used by memtables across multiple column families and/or DB instances.
     /// Users can enable this control by 2 ways:
@@ -3854,6 +4425,218 @@ impl Options {
         let val_u8 = unsafe { ffi::rocksdb_options_get_write_dbid_to_manifest(self.inner) };
         val_u8 != 0
     }
+
+    /// Sets the maximum number of range deletions a memtable may hold before it
+    /// is marked for flush. Range tombstones in the memtable have to be
+    /// consulted by every read, so bounding them keeps reads fast under heavy
+    /// `delete_range` usage.
+    ///
+    /// Default: 0 (no limit)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_memtable_max_range_deletions(100);
+    /// ```
+    pub fn set_memtable_max_range_deletions(&mut self, val: u32) {
+        unsafe {
+            ffi::rocksdb_options_set_memtable_max_range_deletions(self.inner, val);
+        }
+    }
+
+    /// Sets the threshold for experimental in-memory garbage collection
+    /// (mempurge) of memtables.
+    ///
+    /// Instead of flushing a full memtable to an L0 file, RocksDB first tries to
+    /// drop overwritten and deleted entries in memory. This reduces write
+    /// amplification for workloads that repeatedly overwrite a small key set. A
+    /// value of `0.0` disables mempurge; `1.0` is a good starting point when
+    /// enabling it.
+    ///
+    /// Default: 0.0 (disabled)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_experimental_mempurge_threshold(1.0);
+    /// ```
+    pub fn set_experimental_mempurge_threshold(&mut self, threshold: f64) {
+        unsafe {
+            ffi::rocksdb_options_set_experimental_mempurge_threshold(self.inner, threshold);
+        }
+    }
+
+    /// Enables per key-value checksum protection for entries in the memtable,
+    /// to detect in-memory corruption such as bit flips. Each entry carries
+    /// `bytes` bytes of checksum, which are verified on reads, flushes and
+    /// compactions.
+    ///
+    /// Supported values are 0 (disabled), 1, 2, 4 and 8. Other values make
+    /// opening the DB fail.
+    ///
+    /// Default: 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_memtable_protection_bytes_per_key(8);
+    /// ```
+    pub fn set_memtable_protection_bytes_per_key(&mut self, bytes: u8) {
+        self.set_option_via_string("memtable_protection_bytes_per_key", bytes);
+    }
+
+    /// Enables per key-value checksum protection for entries in data blocks
+    /// of SST files while they are held in memory, e.g. in the block cache.
+    /// Unlike the block checksums verified on reads from disk, this catches
+    /// corruption of already loaded blocks.
+    ///
+    /// Supported values are 0 (disabled), 1, 2, 4 and 8. Other values make
+    /// opening the DB fail.
+    ///
+    /// Default: 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_block_protection_bytes_per_key(8);
+    /// ```
+    pub fn set_block_protection_bytes_per_key(&mut self, bytes: u8) {
+        self.set_option_via_string("block_protection_bytes_per_key", bytes);
+    }
+
+    /// Enables consistency checks of the LSM tree structure, e.g. that files
+    /// within a level don't overlap, whenever the set of live files changes.
+    /// A violation is reported as a corruption error instead of silently
+    /// continuing with an inconsistent tree.
+    ///
+    /// Default: true
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_force_consistency_checks(true);
+    /// ```
+    pub fn set_force_consistency_checks(&mut self, enabled: bool) {
+        self.set_option_via_string("force_consistency_checks", enabled);
+    }
+
+    /// If enabled, opening a DB whose files are partially missing or corrupted
+    /// recovers to the most recent consistent point-in-time state that can be
+    /// reconstructed from the available SST files, instead of failing.
+    ///
+    /// This only affects `DB::open`; recovery from background errors at
+    /// runtime is controlled by [`Options::set_max_bgerror_resume_count`].
+    ///
+    /// Default: false
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_best_efforts_recovery(true);
+    /// ```
+    pub fn set_best_efforts_recovery(&mut self, enabled: bool) {
+        self.set_option_via_string("best_efforts_recovery", enabled);
+    }
+
+    /// Sets the maximum number of times RocksDB tries to automatically resume
+    /// the DB after a retryable background error, such as a transient IO
+    /// error during flush or compaction. Each attempt is counted by the
+    /// `ErrorHandlerAutoresume*` statistics. A value of 0 or less disables
+    /// auto-resume.
+    ///
+    /// Default: `i32::MAX`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_max_bgerror_resume_count(10);
+    /// ```
+    pub fn set_max_bgerror_resume_count(&mut self, count: i32) {
+        self.set_option_via_string("max_bgerror_resume_count", count);
+    }
+
+    /// Sets the interval in microseconds between two auto-resume attempts
+    /// after a retryable background error.
+    ///
+    /// Default: 1000000 (1 second)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_bgerror_resume_retry_interval(500_000);
+    /// ```
+    pub fn set_bgerror_resume_retry_interval(&mut self, micros: u64) {
+        self.set_option_via_string("bgerror_resume_retry_interval", micros);
+    }
+
+    /// Sets the maximum size in bytes of a write batch group. When many
+    /// threads write concurrently, the leader of a write group merges the
+    /// batches of the waiting writers into a single WAL write and memtable
+    /// insertion, up to this size. Larger groups amortize the WAL write over
+    /// more batches, smaller groups bound the latency of the leader.
+    ///
+    /// Default: 1048576 (1MiB)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_max_write_batch_group_size_bytes(4 * 1024 * 1024);
+    /// ```
+    pub fn set_max_write_batch_group_size_bytes(&mut self, bytes: u64) {
+        unsafe {
+            ffi::rocksdb_options_set_max_write_batch_group_size_bytes(self.inner, bytes);
+        }
+    }
+
+    /// Returns the value of the `max_write_batch_group_size_bytes` option.
+    pub fn get_max_write_batch_group_size_bytes(&self) -> u64 {
+        unsafe { ffi::rocksdb_options_get_max_write_batch_group_size_bytes(self.inner) }
+    }
+
+    /// If true, RocksDB may include user keys and values in error messages and
+    /// logs, e.g. when reporting corruption. Keep it disabled if keys or values
+    /// are sensitive and must not leak into logs.
+    ///
+    /// Default: false
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_allow_data_in_errors(false);
+    /// ```
+    pub fn set_allow_data_in_errors(&mut self, allow: bool) {
+        self.set_option_via_string("allow_data_in_errors", allow);
+    }
 }
 
 impl Default for Options {
@@ -3877,6 +4660,11 @@ impl FlushOptions {
 
     /// Waits until the flush is done.
     ///
+    /// RocksDB's `FlushOptions::allow_write_stall` is not exposed by the C
+    /// API, so a flush always proceeds even if it causes a write stall. Use
+    /// `set_wait(false)` to at least keep the calling thread from blocking
+    /// on the flush.
+    ///
     /// Default: true
     ///
     /// # Examples
@@ -4155,6 +4943,11 @@ impl ReadOptions {
     /// resides on a particular cache. If the required data is not
     /// found at the specified cache, then Status::Incomplete is returned.
     ///
+    /// Such reads fail with an error of kind
+    /// [`ErrorKind::Incomplete`](crate::ErrorKind::Incomplete) instead of
+    /// blocking on IO, which makes `ReadTier::BlockCache` suitable for a
+    /// non-blocking fast path that falls back to a regular read.
+    ///
     /// Default: ::All
     pub fn set_read_tier(&mut self, tier: ReadTier) {
         unsafe {
@@ -4189,6 +4982,19 @@ impl ReadOptions {
         }
     }
 
+    /// When true, by default use total_order_seek = true, and RocksDB can
+    /// selectively enable prefix seek mode if won't generate a different result
+    /// from total_order_seek, based on seek key, and iterator upper bound.
+    /// This lets a single `ReadOptions` be used for both bounded scans that
+    /// stay within a prefix and scans that need total order.
+    ///
+    /// Default: false
+    pub fn set_auto_prefix_mode(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_auto_prefix_mode(self.inner, c_uchar::from(v));
+        }
+    }
+
     /// Sets a threshold for the number of keys that can be skipped
     /// before failing an iterator seek as incomplete. The default value of 0 should be used to
     /// never fail a request as incomplete, even on skipping too many keys.
@@ -4217,6 +5023,10 @@ impl ReadOptions {
     /// readers until they are naturally deleted during compaction. This improves
     /// read performance in DBs with many range deletions.
     ///
+    /// This is also useful for debugging, to see which keys shadowed by a
+    /// range deletion are still physically present. Keys that have already
+    /// been dropped by a flush or compaction are not resurrected.
+    ///
     /// Default: false
     pub fn set_ignore_range_deletions(&mut self, v: bool) {
         unsafe {
@@ -4270,6 +5080,12 @@ impl ReadOptions {
     /// If true, create a tailing iterator. Note that tailing iterators
     /// only support moving in the forward direction. Iterating in reverse
     /// or seek_to_last are not supported.
+    ///
+    /// Instead of reading from an implicit snapshot taken at creation, a
+    /// tailing iterator sees data written after it was created: moving
+    /// forward from its current position returns keys that have since been
+    /// inserted after it. Once it has run past the last key it is no longer
+    /// valid; seek again to pick up newer writes.
     pub fn set_tailing(&mut self, v: bool) {
         unsafe {
             ffi::rocksdb_readoptions_set_tailing(self.inner, c_uchar::from(v));
@@ -4568,6 +5384,28 @@ pub enum DBRecoveryMode {
     SkipAnyCorruptedRecord = ffi::rocksdb_skip_any_corrupted_records_recovery as isize,
 }
 
+/// Used by [`Options::set_prepopulate_blob_cache`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(i32)]
+pub enum PrepopulateBlobCache {
+    Disable = 0,
+    FlushOnly = 1,
+}
+
+/// What an [in-place update callback](Options::set_inplace_update_callback)
+/// stores for a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InplaceUpdateResult {
+    /// Leaves the key unchanged, dropping the value that was put.
+    Failed,
+    /// The existing value was changed in place and now has the given length,
+    /// which can't exceed its old length. Treated as `Failed` otherwise, or
+    /// if there was no existing value.
+    UpdatedInplace(usize),
+    /// Stores the given value as a new entry.
+    Updated(Vec<u8>),
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(i32)]
 pub enum RateLimiterMode {
@@ -4631,6 +5469,23 @@ impl FifoCompactOptions {
             ffi::rocksdb_fifo_compaction_options_set_max_table_files_size(self.inner, nbytes);
         }
     }
+
+    /// If true, try to do compaction to compact smaller files into larger ones.
+    /// Minimum files to compact follows
+    /// [`Options::set_level_zero_file_num_compaction_trigger`] and compaction
+    /// won't trigger if average compact bytes per del file is larger than
+    /// [`Options::set_write_buffer_size`]. This is to protect large files from
+    /// being compacted again.
+    ///
+    /// Default: false
+    pub fn set_allow_compaction(&mut self, allow_compaction: bool) {
+        unsafe {
+            ffi::rocksdb_fifo_compaction_options_set_allow_compaction(
+                self.inner,
+                c_uchar::from(allow_compaction),
+            );
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -4796,6 +5651,12 @@ impl CompactOptions {
     /// for the scheduled manual compaction to complete. If exclusive_manual_compaction
     /// is set to true, the call will disable scheduling of automatic compaction jobs
     /// and wait for existing automatic compaction jobs to finish.
+    ///
+    /// The number of subcompactions used by a manual compaction follows
+    /// [`Options::set_max_subcompactions`]; the C API does not allow
+    /// overriding it per call.
+    ///
+    /// Default: true
     pub fn set_exclusive_manual_compaction(&mut self, v: bool) {
         unsafe {
             ffi::rocksdb_compactoptions_set_exclusive_manual_compaction(
@@ -106,6 +106,11 @@ impl Env {
     }
 
     /// Wait for all threads started by StartThread to terminate.
+    ///
+    /// This does not wait for flushes and compactions queued in the thread
+    /// pools. To drain a shared `Env` before shutdown, call
+    /// [`DB::cancel_all_background_work`](crate::DB::cancel_all_background_work)
+    /// with `wait` set on every DB using it.
     pub fn join_all_threads(&mut self) {
         unsafe {
             ffi::rocksdb_env_join_all_threads(self.0.inner);
@@ -214,6 +214,14 @@ impl CSlice {
     pub(crate) unsafe fn from_raw_parts(data: *const c_char, len: size_t) -> Self {
         Self { data, len }
     }
+
+    /// Same as `from_raw_parts`, but returns `None` if `data` is null.
+    ///
+    /// # Safety
+    /// Same requirements as `from_raw_parts` for a non-null `data`.
+    pub(crate) unsafe fn from_raw(data: *const c_char, len: size_t) -> Option<Self> {
+        (!data.is_null()).then(|| Self::from_raw_parts(data, len))
+    }
 }
 
 impl AsRef<[u8]> for CSlice {
@@ -230,6 +238,18 @@ impl Drop for CSlice {
     }
 }
 
+#[test]
+fn test_c_slice_from_raw() {
+    assert!(unsafe { CSlice::from_raw(ptr::null(), 0) }.is_none());
+
+    let slice = unsafe {
+        let data = libc::malloc(3) as *mut c_char;
+        ptr::copy_nonoverlapping(b"foo".as_ptr() as *const c_char, data, 3);
+        CSlice::from_raw(data, 3).unwrap()
+    };
+    assert_eq!(slice.as_ref(), b"foo");
+}
+
 #[test]
 fn test_c_str_like_bake() {
     fn test<S: CStrLike>(value: S) -> Result<usize, S::Error> {
@@ -89,6 +89,7 @@ pub mod checkpoint;
 mod column_family;
 pub mod compaction_filter;
 pub mod compaction_filter_factory;
+mod compaction_stats;
 mod comparator;
 mod db;
 mod db_iterator;
@@ -113,29 +114,32 @@ pub use crate::{
         ColumnFamilyRef, ColumnFamilyTtl, DEFAULT_COLUMN_FAMILY_NAME,
     },
     compaction_filter::Decision as CompactionDecision,
+    compaction_stats::{CompactionStats, LevelCompactionStats},
     db::{
-        DBAccess, DBCommon, DBWithThreadMode, LiveFile, MultiThreaded, SingleThreaded, ThreadMode,
-        DB,
+        CfStats, DBAccess, DBCommon, DBWithThreadMode, GetIntoBufferResult, LiveFile,
+        MultiThreaded, SingleThreaded, SizeApproximationFlags, ThreadMode, DB,
     },
     db_iterator::{
-        DBIterator, DBIteratorWithThreadMode, DBRawIterator, DBRawIteratorWithThreadMode,
-        DBWALIterator, Direction, IteratorMode,
+        DBIterator, DBIteratorWithThreadMode, DBKeyIterator, DBKeyIteratorWithThreadMode,
+        DBRawIterator, DBRawIteratorWithThreadMode, DBTimestampedIterator,
+        DBTimestampedIteratorWithThreadMode, DBWALIterator, Direction, IteratorMode,
     },
     db_options::{
         BlockBasedIndexType, BlockBasedOptions, BlockBasedPinningTier, BottommostLevelCompaction,
-        Cache, ChecksumType, CompactOptions, CuckooTableOptions, DBCompactionPri,
+        Cache, ChecksumType, CompactOptions, Comparator, CuckooTableOptions, DBCompactionPri,
         DBCompactionStyle, DBCompressionType, DBPath, DBRecoveryMode, DataBlockIndexType,
-        FifoCompactOptions, FlushOptions, IngestExternalFileOptions, KeyEncodingType, LogLevel,
-        LruCacheOptions, MemtableFactory, Options, PlainTableFactoryOptions, RateLimiterMode,
-        ReadOptions, ReadTier, UniversalCompactOptions, UniversalCompactionStopStyle,
-        WaitForCompactOptions, WriteBufferManager, WriteOptions,
+        FifoCompactOptions, FlushOptions, IngestExternalFileOptions, InplaceUpdateResult,
+        KeyEncodingType, LogLevel, LruCacheOptions, MemtableFactory, Options,
+        PlainTableFactoryOptions, PrepopulateBlobCache, RateLimiterMode, ReadOptions, ReadTier,
+        UniversalCompactOptions, UniversalCompactionStopStyle, WaitForCompactOptions,
+        WriteBufferManager, WriteOptions,
     },
     db_pinnable_slice::DBPinnableSlice,
     env::Env,
     ffi_util::CStrLike,
     iter_range::{IterateBounds, PrefixRange},
     merge_operator::MergeOperands,
-    perf::{PerfContext, PerfMetric, PerfStatsLevel},
+    perf::{PerfContext, PerfContextSnapshot, PerfMetric, PerfStatsLevel},
     slice_transform::SliceTransform,
     snapshot::{Snapshot, SnapshotWithThreadMode},
     sst_file_writer::SstFileWriter,
@@ -143,7 +147,9 @@ pub use crate::{
         OptimisticTransactionDB, OptimisticTransactionOptions, Transaction, TransactionDB,
         TransactionDBOptions, TransactionOptions,
     },
-    write_batch::{WriteBatch, WriteBatchIterator, WriteBatchWithTransaction},
+    write_batch::{
+        WriteBatch, WriteBatchIterator, WriteBatchIteratorCf, WriteBatchWithTransaction,
+    },
 };
 
 use rust_librocksdb_sys as ffi;
@@ -152,7 +158,7 @@ use std::error;
 use std::fmt;
 
 /// RocksDB error kind.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum ErrorKind {
     NotFound,
     Corruption,
@@ -189,6 +195,10 @@ impl Error {
     }
 
     /// Parse corresponding [`ErrorKind`] from error message.
+    ///
+    /// This is the way to tell retryable failures apart, e.g. a transaction
+    /// that failed with [`ErrorKind::Busy`] or [`ErrorKind::TimedOut`] can be
+    /// retried, while [`ErrorKind::Corruption`] can't.
     pub fn kind(&self) -> ErrorKind {
         match self.message.split(':').next().unwrap_or("") {
             "NotFound" => ErrorKind::NotFound,
@@ -322,4 +332,27 @@ mod test {
         is_sync::<WriteBufferManager>();
         is_sync::<WriteBufferManagerWrapper>();
     }
+
+    #[test]
+    fn error_kind() {
+        use super::{Error, ErrorKind};
+
+        let kind = |message: &str| Error::new(message.to_owned()).kind();
+        assert_eq!(kind("NotFound: "), ErrorKind::NotFound);
+        assert_eq!(
+            kind("Corruption: block checksum mismatch"),
+            ErrorKind::Corruption
+        );
+        assert_eq!(kind("Resource busy: "), ErrorKind::Busy);
+        assert_eq!(
+            kind("Operation timed out: Timeout waiting to lock key"),
+            ErrorKind::TimedOut
+        );
+        assert_eq!(
+            kind("Result incomplete: no blocking io"),
+            ErrorKind::Incomplete
+        );
+        assert_eq!(kind("Operation failed. Try again.: "), ErrorKind::TryAgain);
+        assert_eq!(kind("Could not initialize database."), ErrorKind::Unknown);
+    }
 }
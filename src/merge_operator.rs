@@ -168,6 +168,73 @@ pub unsafe extern "C" fn partial_merge_callback<F: MergeFn, PF: MergeFn>(
     )
 }
 
+/// Returns a merge operator that adds up little-endian `u64` counters,
+/// wrapping on overflow.
+///
+/// A key without an existing value starts at 0. The merge fails if the
+/// existing value or an operand is not exactly 8 bytes long. Addition is
+/// associative, so the operator is meant to be passed to
+/// [`Options::set_merge_operator_associative`](crate::Options::set_merge_operator_associative).
+///
+/// ```
+/// use rust_rocksdb::{merge_operator, Options};
+///
+/// let mut opts = Options::default();
+/// opts.set_merge_operator_associative("u64_add", merge_operator::u64_add());
+/// ```
+pub fn u64_add() -> impl MergeFn + Clone {
+    |_key: &[u8], existing_val: Option<&[u8]>, operands: &MergeOperands| {
+        let mut sum = match existing_val {
+            Some(value) => u64::from_le_bytes(value.try_into().ok()?),
+            None => 0,
+        };
+        for op in operands {
+            sum = sum.wrapping_add(u64::from_le_bytes(op.try_into().ok()?));
+        }
+        Some(sum.to_le_bytes().to_vec())
+    }
+}
+
+/// Returns a merge operator that adds up little-endian `i64` counters,
+/// wrapping on overflow.
+///
+/// Behaves like [`u64_add`] otherwise, so negative operands decrement the
+/// counter.
+pub fn i64_add() -> impl MergeFn + Clone {
+    |_key: &[u8], existing_val: Option<&[u8]>, operands: &MergeOperands| {
+        let mut sum = match existing_val {
+            Some(value) => i64::from_le_bytes(value.try_into().ok()?),
+            None => 0,
+        };
+        for op in operands {
+            sum = sum.wrapping_add(i64::from_le_bytes(op.try_into().ok()?));
+        }
+        Some(sum.to_le_bytes().to_vec())
+    }
+}
+
+/// Returns a merge operator that appends operands to the existing value,
+/// optionally joining them with `separator`.
+///
+/// A key without an existing value starts with the first operand, so no
+/// leading separator is written. Concatenation is associative, so the operator
+/// is meant to be passed to
+/// [`Options::set_merge_operator_associative`](crate::Options::set_merge_operator_associative).
+pub fn concat(separator: Option<u8>) -> impl MergeFn + Clone {
+    move |_key: &[u8], existing_val: Option<&[u8]>, operands: &MergeOperands| {
+        let capacity = existing_val.map_or(0, <[u8]>::len)
+            + operands.iter().map(|op| op.len() + 1).sum::<usize>();
+        let mut result = Vec::with_capacity(capacity);
+        for (i, value) in existing_val.into_iter().chain(operands).enumerate() {
+            if let (Some(separator), true) = (separator, i > 0) {
+                result.push(separator);
+            }
+            result.extend_from_slice(value);
+        }
+        Some(result)
+    }
+}
+
 pub struct MergeOperands {
     operands_list: *const *const c_char,
     operands_list_len: *const size_t,
@@ -165,6 +165,44 @@ impl PerfContext {
     pub fn metric(&self, id: PerfMetric) -> u64 {
         unsafe { ffi::rocksdb_perfcontext_metric(self.inner, id as c_int) }
     }
+
+    /// Reads the current value of every metric at once
+    pub fn snapshot(&self) -> PerfContextSnapshot {
+        let mut values = [0; PerfMetric::TotalMetricCount as usize];
+        for (id, value) in values.iter_mut().enumerate() {
+            *value = unsafe { ffi::rocksdb_perfcontext_metric(self.inner, id as c_int) };
+        }
+        PerfContextSnapshot { values }
+    }
+}
+
+/// Values of all perf metrics captured at one point in time by
+/// [`PerfContext::snapshot`].
+///
+/// Two snapshots can be compared with [`PerfContextSnapshot::diff`] to
+/// attribute the cost of the work done in between.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PerfContextSnapshot {
+    values: [u64; PerfMetric::TotalMetricCount as usize],
+}
+
+impl PerfContextSnapshot {
+    /// Returns value of a metric
+    pub fn metric(&self, id: PerfMetric) -> u64 {
+        self.values.get(id as usize).copied().unwrap_or(0)
+    }
+
+    /// Returns the per-metric increase since the `earlier` snapshot.
+    ///
+    /// Metrics that went down, e.g. because the context was reset in
+    /// between, are reported as 0.
+    pub fn diff(&self, earlier: &PerfContextSnapshot) -> PerfContextSnapshot {
+        let mut values = self.values;
+        for (value, earlier) in values.iter_mut().zip(earlier.values.iter()) {
+            *value = value.saturating_sub(*earlier);
+        }
+        PerfContextSnapshot { values }
+    }
 }
 
 /// Memory usage stats
@@ -39,7 +39,7 @@ pub const SSTABLES: &PropName = property!("sstables");
 /// "rocksdb.cfstats" - Both of "rocksdb.cfstats-no-file-histogram" and
 /// "rocksdb.cf-file-histogram" together. See below for description
 /// of the two.
-pub const CFSTATS: &PropName = property!("CFSTATS");
+pub const CFSTATS: &PropName = property!("cfstats");
 
 /// "rocksdb.cfstats-no-file-histogram" - returns a multi-line string with
 /// general column family stats per-level over db's lifetime ("`L<n>`"),
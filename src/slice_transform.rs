@@ -23,8 +23,23 @@ use crate::{ffi, ffi_util::CStrLike};
 /// to another. Its primary use-case is in configuring rocksdb
 /// to store prefix blooms by setting prefix_extractor in
 /// ColumnFamilyOptions.
+///
+/// Build it with one of the constructors below; the struct can't be built
+/// with a struct literal from outside this crate.
 pub struct SliceTransform {
     pub inner: *mut ffi::rocksdb_slicetransform_t,
+    kind: Kind,
+}
+
+/// The RocksDB C API can't call back into a transform, so we keep enough
+/// information to apply it from Rust.
+enum Kind {
+    FixedPrefix(usize),
+    Noop,
+    Custom {
+        transform_fn: Option<PrefixFn>,
+        in_domain_fn: Option<InDomainFn>,
+    },
 }
 
 // NB we intentionally don't implement a Drop that passes
@@ -38,6 +53,40 @@ impl SliceTransform {
         name: impl CStrLike,
         transform_fn: TransformFn,
         in_domain_fn: Option<InDomainFn>,
+    ) -> SliceTransform {
+        Self::create_custom(name, transform_fn, None, in_domain_fn)
+    }
+
+    /// Same as [`create`](Self::create), but `transform_fn` must work for
+    /// keys of any lifetime, which lets [`transform`](Self::transform) call
+    /// it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::SliceTransform;
+    ///
+    /// fn before_dot(key: &[u8]) -> &[u8] {
+    ///     let end = key.iter().position(|&b| b == b'.').unwrap_or(key.len());
+    ///     &key[..end]
+    /// }
+    ///
+    /// let transform = SliceTransform::create_with_prefix_fn("before_dot", before_dot, None);
+    /// assert_eq!(transform.transform(b"user.42"), Some(&b"user"[..]));
+    /// ```
+    pub fn create_with_prefix_fn(
+        name: impl CStrLike,
+        transform_fn: PrefixFn,
+        in_domain_fn: Option<InDomainFn>,
+    ) -> SliceTransform {
+        Self::create_custom(name, transform_fn, Some(transform_fn), in_domain_fn)
+    }
+
+    fn create_custom(
+        name: impl CStrLike,
+        transform_fn: TransformFn,
+        prefix_fn: Option<PrefixFn>,
+        in_domain_fn: Option<InDomainFn>,
     ) -> SliceTransform {
         let cb = Box::into_raw(Box::new(TransformCallback {
             name: name.into_c_string().unwrap(),
@@ -57,23 +106,80 @@ impl SliceTransform {
             )
         };
 
-        SliceTransform { inner: st }
+        SliceTransform {
+            inner: st,
+            kind: Kind::Custom {
+                transform_fn: prefix_fn,
+                in_domain_fn,
+            },
+        }
     }
 
     pub fn create_fixed_prefix(len: size_t) -> SliceTransform {
         SliceTransform {
             inner: unsafe { ffi::rocksdb_slicetransform_create_fixed_prefix(len) },
+            kind: Kind::FixedPrefix(len),
         }
     }
 
     pub fn create_noop() -> SliceTransform {
         SliceTransform {
             inner: unsafe { ffi::rocksdb_slicetransform_create_noop() },
+            kind: Kind::Noop,
+        }
+    }
+
+    /// Returns the prefix RocksDB derives from `key`, e.g. for prefix bloom
+    /// filters and prefix seeks.
+    ///
+    /// `key` must be [in the domain](Self::in_domain) of the transform.
+    ///
+    /// Returns `None` for transforms built with [`create`](Self::create):
+    /// their [`TransformFn`] isn't guaranteed to work for keys of any lifetime,
+    /// so it can't be called safely from here. Use
+    /// [`create_with_prefix_fn`](Self::create_with_prefix_fn) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_rocksdb::SliceTransform;
+    ///
+    /// let transform = SliceTransform::create_fixed_prefix(4);
+    /// assert_eq!(transform.transform(b"abcdefgh"), Some(&b"abcd"[..]));
+    /// ```
+    pub fn transform<'k>(&self, key: &'k [u8]) -> Option<&'k [u8]> {
+        match self.kind {
+            Kind::FixedPrefix(len) => Some(&key[..len.min(key.len())]),
+            Kind::Noop => Some(key),
+            Kind::Custom { transform_fn, .. } => transform_fn.map(|f| f(key)),
+        }
+    }
+
+    /// Returns whether `key` can be transformed. Keys outside of the domain,
+    /// e.g. keys shorter than a fixed prefix, don't take part in prefix
+    /// filtering.
+    pub fn in_domain(&self, key: &[u8]) -> bool {
+        match self.kind {
+            Kind::FixedPrefix(len) => key.len() >= len,
+            Kind::Noop => true,
+            Kind::Custom { in_domain_fn, .. } => in_domain_fn.map_or(true, |f| f(key)),
+        }
+    }
+
+    /// The name RocksDB parses back into this transform from an options
+    /// string. Custom transforms can't be described that way.
+    pub(crate) fn options_string_value(&self) -> Option<String> {
+        match self.kind {
+            Kind::FixedPrefix(len) => Some(format!("fixed:{len}")),
+            Kind::Noop => Some("rocksdb.Noop".to_owned()),
+            Kind::Custom { .. } => None,
         }
     }
 }
 
 pub type TransformFn<'a> = fn(&'a [u8]) -> &'a [u8];
+/// A [`TransformFn`] that works for keys of any lifetime.
+pub type PrefixFn = for<'k> fn(&'k [u8]) -> &'k [u8];
 pub type InDomainFn = fn(&[u8]) -> bool;
 
 pub struct TransformCallback<'a> {
@@ -22,6 +22,13 @@ pub type Snapshot<'a> = SnapshotWithThreadMode<'a, DB>;
 
 /// A consistent view of the database at the point of creation.
 ///
+/// The read methods of a snapshot, such as [`get`](Self::get),
+/// [`multi_get`](Self::multi_get) and [`iterator`](Self::iterator), only see
+/// data written before it was taken, so repeated reads return the same
+/// results without setting [`ReadOptions::set_snapshot`] by hand. The `*_opt`
+/// variants bind the given read options to the snapshot as well. The
+/// snapshot is released when dropped.
+///
 /// # Examples
 ///
 /// ```
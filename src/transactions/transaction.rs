@@ -749,6 +749,9 @@ impl<DB> Transaction<'_, DB> {
         Ok(())
     }
 
+    /// Opens an iterator over the default column family. The iterator sees the
+    /// writes staged in this transaction overlaid on the database, so keys
+    /// deleted within the transaction are skipped.
     pub fn iterator<'a: 'b, 'b>(
         &'a self,
         mode: IteratorMode,
@@ -867,6 +870,8 @@ impl<DB> Transaction<'_, DB> {
         DBRawIteratorWithThreadMode::new_cf(self, cf_handle.inner(), readopts)
     }
 
+    /// Returns a copy of the write batch holding the operations staged in this
+    /// transaction so far.
     pub fn get_writebatch(&self) -> WriteBatchWithTransaction<true> {
         unsafe {
             let wi = ffi::rocksdb_transaction_get_writebatch_wi(self.inner);
@@ -878,6 +883,9 @@ impl<DB> Transaction<'_, DB> {
         }
     }
 
+    /// Stages every operation of `writebatch` into this transaction, as if
+    /// they had been issued through [`put`](Self::put),
+    /// [`delete`](Self::delete) and friends.
     pub fn rebuild_from_writebatch(
         &self,
         writebatch: &WriteBatchWithTransaction<true>,
@@ -82,7 +82,8 @@ pub struct TransactionDB<T: ThreadMode = DefaultThreadMode> {
     path: PathBuf,
     // prepared 2pc transactions.
     prepared: Mutex<Vec<*mut rocksdb_transaction_t>>,
-    _outlive: Vec<OptionsMustOutliveDB>,
+    // Grows with the options of column families created after opening.
+    outlive: Mutex<Vec<OptionsMustOutliveDB>>,
 }
 
 unsafe impl<T: ThreadMode> Send for TransactionDB<T> {}
@@ -321,7 +322,7 @@ impl<T: ThreadMode> TransactionDB<T> {
             cfs: T::new_cf_map_internal(cf_map),
             path: path.as_ref().to_path_buf(),
             prepared: Mutex::new(prepared),
-            _outlive: outlive,
+            outlive: Mutex::new(outlive),
         })
     }
 
@@ -372,13 +373,15 @@ impl<T: ThreadMode> TransactionDB<T> {
             Error::new("Failed to convert path to CString when creating cf".to_owned())
         })?;
 
-        Ok(unsafe {
+        let inner = unsafe {
             ffi_try!(ffi::rocksdb_transactiondb_create_column_family(
                 self.inner,
                 opts.inner,
                 cf_name.as_ptr(),
             ))
-        })
+        };
+        self.outlive.lock().unwrap().push(opts.outlive.clone());
+        Ok(inner)
     }
 
     pub fn list_cf<P: AsRef<Path>>(opts: &Options, path: P) -> Result<Vec<String>, Error> {
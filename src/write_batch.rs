@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{ffi, AsColumnFamilyRef};
+use crate::{ffi, AsColumnFamilyRef, Error};
 use libc::{c_char, c_void, size_t};
 use std::slice;
 
@@ -92,6 +92,90 @@ unsafe extern "C" fn writebatch_delete_callback(state: *mut c_void, k: *const c_
     leaked_cb.delete(key.to_vec().into_boxed_slice());
 }
 
+/// Receives the operations of a write batch together with the id of the
+/// column family they apply to.
+///
+/// The application must provide an implementation of this trait when
+/// iterating the operations within a `WriteBatch` with
+/// [`iterate_cf`](WriteBatchWithTransaction::iterate_cf). The default column
+/// family has id 0.
+pub trait WriteBatchIteratorCf {
+    /// Called with a key and value that were `put` into the batch.
+    fn put_cf(&mut self, cf_id: u32, key: &[u8], value: &[u8]);
+    /// Called with a key that was `delete`d from the batch.
+    fn delete_cf(&mut self, cf_id: u32, key: &[u8]);
+    /// Called with a key that was single deleted from the batch.
+    fn single_delete_cf(&mut self, cf_id: u32, key: &[u8]);
+    /// Called with the bounds of a `delete_range` in the batch.
+    fn delete_range_cf(&mut self, cf_id: u32, from: &[u8], to: &[u8]);
+    /// Called with a key and value that were `merge`d into the batch.
+    fn merge_cf(&mut self, cf_id: u32, key: &[u8], value: &[u8]);
+}
+
+unsafe extern "C" fn writebatch_put_cf_callback(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: usize,
+    v: *const c_char,
+    vlen: usize,
+) {
+    let callbacks = &mut *(state as *mut &mut dyn WriteBatchIteratorCf);
+    let key = slice::from_raw_parts(k as *const u8, klen);
+    let value = slice::from_raw_parts(v as *const u8, vlen);
+    callbacks.put_cf(cf_id, key, value);
+}
+
+unsafe extern "C" fn writebatch_delete_cf_callback(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: usize,
+) {
+    let callbacks = &mut *(state as *mut &mut dyn WriteBatchIteratorCf);
+    let key = slice::from_raw_parts(k as *const u8, klen);
+    callbacks.delete_cf(cf_id, key);
+}
+
+unsafe extern "C" fn writebatch_single_delete_cf_callback(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: usize,
+) {
+    let callbacks = &mut *(state as *mut &mut dyn WriteBatchIteratorCf);
+    let key = slice::from_raw_parts(k as *const u8, klen);
+    callbacks.single_delete_cf(cf_id, key);
+}
+
+unsafe extern "C" fn writebatch_delete_range_cf_callback(
+    state: *mut c_void,
+    cf_id: u32,
+    from: *const c_char,
+    from_len: usize,
+    to: *const c_char,
+    to_len: usize,
+) {
+    let callbacks = &mut *(state as *mut &mut dyn WriteBatchIteratorCf);
+    let from = slice::from_raw_parts(from as *const u8, from_len);
+    let to = slice::from_raw_parts(to as *const u8, to_len);
+    callbacks.delete_range_cf(cf_id, from, to);
+}
+
+unsafe extern "C" fn writebatch_merge_cf_callback(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: usize,
+    v: *const c_char,
+    vlen: usize,
+) {
+    let callbacks = &mut *(state as *mut &mut dyn WriteBatchIteratorCf);
+    let key = slice::from_raw_parts(k as *const u8, klen);
+    let value = slice::from_raw_parts(v as *const u8, vlen);
+    callbacks.merge_cf(cf_id, key, value);
+}
+
 impl<const TRANSACTION: bool> WriteBatchWithTransaction<TRANSACTION> {
     /// Create a new `WriteBatch` without allocating memory.
     pub fn new() -> Self {
@@ -168,6 +252,29 @@ impl<const TRANSACTION: bool> WriteBatchWithTransaction<TRANSACTION> {
         }
     }
 
+    /// Iterate the operations within this write batch, across all column
+    /// families. Like [`iterate`](Self::iterate), this invokes the member
+    /// functions of the provided `WriteBatchIteratorCf` trait implementation
+    /// in the order the operations were added.
+    ///
+    /// Returns an error if the batch is corrupted or holds an operation the
+    /// trait has no callback for, e.g. a wide-column put, after the
+    /// operations before it have been reported.
+    pub fn iterate_cf(&self, mut callbacks: &mut dyn WriteBatchIteratorCf) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_shim_writebatch_iterate_cf(
+                self.inner,
+                &mut callbacks as *mut &mut dyn WriteBatchIteratorCf as *mut c_void,
+                Some(writebatch_put_cf_callback),
+                Some(writebatch_delete_cf_callback),
+                Some(writebatch_single_delete_cf_callback),
+                Some(writebatch_delete_range_cf_callback),
+                Some(writebatch_merge_cf_callback),
+            ));
+        }
+        Ok(())
+    }
+
     /// Insert a value into the database under the given key.
     pub fn put<K, V>(&mut self, key: K, value: V)
     where
@@ -211,7 +318,17 @@ impl<const TRANSACTION: bool> WriteBatchWithTransaction<TRANSACTION> {
 
     /// Insert a value into the specific column family of the database
     /// under the given key with timestamp.
-    pub fn put_cf_with_ts<K, V, S>(&mut self, cf: &impl AsColumnFamilyRef, key: K, ts: S, value: V)
+    ///
+    /// Returns an `InvalidArgument` error, and leaves the batch unchanged, if
+    /// `ts` is not as long as the timestamp size of the column family's
+    /// comparator.
+    pub fn put_cf_with_ts<K, V, S>(
+        &mut self,
+        cf: &impl AsColumnFamilyRef,
+        key: K,
+        ts: S,
+        value: V,
+    ) -> Result<(), Error>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
@@ -220,6 +337,7 @@ impl<const TRANSACTION: bool> WriteBatchWithTransaction<TRANSACTION> {
         let key = key.as_ref();
         let value = value.as_ref();
         let ts = ts.as_ref();
+        let len = self.len();
         unsafe {
             ffi::rocksdb_writebatch_put_cf_with_ts(
                 self.inner,
@@ -232,6 +350,7 @@ impl<const TRANSACTION: bool> WriteBatchWithTransaction<TRANSACTION> {
                 value.len() as size_t,
             );
         }
+        self.check_ts_accepted(cf, len, ts)
     }
 
     pub fn merge<K, V>(&mut self, key: K, value: V)
@@ -303,14 +422,19 @@ impl<const TRANSACTION: bool> WriteBatchWithTransaction<TRANSACTION> {
 
     /// Removes the database entry in the specific column family with timestamp for key.
     /// Does nothing if the key was not found.
+    ///
+    /// Returns an `InvalidArgument` error, and leaves the batch unchanged, if
+    /// `ts` is not as long as the timestamp size of the column family's
+    /// comparator.
     pub fn delete_cf_with_ts<K: AsRef<[u8]>, S: AsRef<[u8]>>(
         &mut self,
         cf: &impl AsColumnFamilyRef,
         key: K,
         ts: S,
-    ) {
+    ) -> Result<(), Error> {
         let key = key.as_ref();
         let ts = ts.as_ref();
+        let len = self.len();
         unsafe {
             ffi::rocksdb_writebatch_delete_cf_with_ts(
                 self.inner,
@@ -321,6 +445,51 @@ impl<const TRANSACTION: bool> WriteBatchWithTransaction<TRANSACTION> {
                 ts.len() as size_t,
             );
         }
+        self.check_ts_accepted(cf, len, ts)
+    }
+
+    /// The C API drops the status of timestamped batch operations, so a
+    /// rejected timestamp only shows as an operation missing from the batch.
+    fn check_ts_accepted(
+        &self,
+        cf: &impl AsColumnFamilyRef,
+        len: usize,
+        ts: &[u8],
+    ) -> Result<(), Error> {
+        if self.len() == len + 1 {
+            return Ok(());
+        }
+        let expected = timestamp_size(cf);
+        if expected != ts.len() {
+            return Err(Error::new(format!(
+                "Invalid argument: timestamp size mismatch: expected {expected} bytes, got {}",
+                ts.len()
+            )));
+        }
+        Err(Error::new(
+            "Invalid argument: the timestamped operation was rejected".to_owned(),
+        ))
+    }
+
+    /// Append a blob of arbitrary size to the records in this batch. The blob
+    /// is stored in the WAL alongside the batch, but is not applied to the
+    /// database and doesn't count towards [`len`](Self::len). This is useful
+    /// to annotate batches, e.g. with a transaction id, for consumers of
+    /// [`DB::get_updates_since`](crate::DB::get_updates_since).
+    ///
+    /// The blob is part of the serialized batch returned by
+    /// [`data`](Self::data), but [`iterate`](Self::iterate) doesn't report it,
+    /// as the RocksDB C API has no callback for log data.
+    pub fn put_log_data<V: AsRef<[u8]>>(&mut self, blob: V) {
+        let blob = blob.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_put_log_data(
+                self.inner,
+                blob.as_ptr() as *const c_char,
+                blob.len() as size_t,
+            );
+        }
     }
 
     /// Clear all updates buffered in this batch.
@@ -387,3 +556,28 @@ impl<const TRANSACTION: bool> Drop for WriteBatchWithTransaction<TRANSACTION> {
 }
 
 unsafe impl<const TRANSACTION: bool> Send for WriteBatchWithTransaction<TRANSACTION> {}
+
+/// Returns the timestamp size of the comparator of `cf`.
+///
+/// RocksDB stores a put without a timestamp into a column family with
+/// timestamps under the key followed by a zeroed timestamp of that size.
+fn timestamp_size(cf: &impl AsColumnFamilyRef) -> usize {
+    struct KeyLen(usize);
+
+    impl WriteBatchIteratorCf for KeyLen {
+        fn put_cf(&mut self, _cf_id: u32, key: &[u8], _value: &[u8]) {
+            self.0 = key.len();
+        }
+        fn delete_cf(&mut self, _cf_id: u32, _key: &[u8]) {}
+        fn single_delete_cf(&mut self, _cf_id: u32, _key: &[u8]) {}
+        fn delete_range_cf(&mut self, _cf_id: u32, _from: &[u8], _to: &[u8]) {}
+        fn merge_cf(&mut self, _cf_id: u32, _key: &[u8], _value: &[u8]) {}
+    }
+
+    let mut batch = WriteBatch::default();
+    batch.put_cf(cf, b"", b"");
+    let mut key_len = KeyLen(0);
+    // A single put is always reported.
+    let _ = batch.iterate_cf(&mut key_len);
+    key_len.0
+}
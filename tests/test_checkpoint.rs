@@ -16,7 +16,7 @@ mod util;
 
 use pretty_assertions::assert_eq;
 
-use rust_rocksdb::{checkpoint::Checkpoint, Options, DB};
+use rust_rocksdb::{checkpoint::Checkpoint, Options, TransactionDB, TransactionDBOptions, DB};
 use util::DBPath;
 
 #[test]
@@ -105,3 +105,99 @@ fn test_checkpoint_outlive_db() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/fail/checkpoint_outlive_db.rs");
 }
+
+#[test]
+pub fn test_checkpoint_no_flush() {
+    const PATH_PREFIX: &str = "_rust_rocksdb_cp_no_flush_";
+
+    let db_path = DBPath::new(&format!("{PATH_PREFIX}db1"));
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    let db = DB::open(&opts, &db_path).unwrap();
+
+    db.put(b"k1", b"v1").unwrap();
+    db.put(b"k2", b"v2").unwrap();
+
+    // Checkpoint without flushing: the memtable stays in memory and no table
+    // file is written
+    let cp = Checkpoint::new(&db).unwrap();
+    let cp1_path = DBPath::new(&format!("{PATH_PREFIX}cp1"));
+    cp.create_checkpoint_no_flush(&cp1_path).unwrap();
+    assert_eq!(db.live_files().unwrap().len(), 0);
+
+    // A regular checkpoint flushes first
+    let cp2_path = DBPath::new(&format!("{PATH_PREFIX}cp2"));
+    cp.create_checkpoint(&cp2_path).unwrap();
+    assert_eq!(db.live_files().unwrap().len(), 1);
+
+    // Both checkpoints hold the data; the first one replays it from its WAL
+    for path in [&cp1_path, &cp2_path] {
+        let cp_db = DB::open_default(path).unwrap();
+        assert_eq!(cp_db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(cp_db.get(b"k2").unwrap().unwrap(), b"v2");
+    }
+}
+
+#[test]
+pub fn test_transaction_db_checkpoint() {
+    const PATH_PREFIX: &str = "_rust_rocksdb_cp_txn_db_";
+
+    let db_path = DBPath::new(&format!("{PATH_PREFIX}db1"));
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db: TransactionDB =
+        TransactionDB::open_cf(&opts, &TransactionDBOptions::default(), &db_path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+    db.put_cf(&cf1, b"k1", b"v1").unwrap();
+
+    let txn = db.transaction();
+    txn.put_cf(&cf1, b"k2", b"uncommitted").unwrap();
+
+    let cp = Checkpoint::new_transaction_db(&db).unwrap();
+    let cp1_path = DBPath::new(&format!("{PATH_PREFIX}cp1"));
+    cp.create_checkpoint(&cp1_path).unwrap();
+    txn.rollback().unwrap();
+
+    // The checkpoint opens as a plain DB
+    let cp_db = DB::open_cf(&Options::default(), &cp1_path, ["cf1"]).unwrap();
+    let cp_cf1 = cp_db.cf_handle("cf1").unwrap();
+    assert_eq!(cp_db.get_cf(&cp_cf1, b"k1").unwrap().unwrap(), b"v1");
+    assert!(cp_db.get_cf(&cp_cf1, b"k2").unwrap().is_none());
+}
+
+#[test]
+fn test_checkpoint_with_files() {
+    const PATH_PREFIX: &str = "_rust_rocksdb_cp_with_files_";
+
+    let db_path = DBPath::new(&format!("{PATH_PREFIX}db"));
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_disable_auto_compactions(true);
+    let db = DB::open(&opts, &db_path).unwrap();
+    for i in 0..3 {
+        db.put(format!("k{i}"), b"v").unwrap();
+        db.flush().unwrap();
+    }
+
+    let cp_path = DBPath::new(&format!("{PATH_PREFIX}cp"));
+    let files = Checkpoint::new(&db)
+        .unwrap()
+        .create_checkpoint_with_files(&cp_path)
+        .unwrap();
+
+    let mut live_files: Vec<_> = db
+        .live_files()
+        .unwrap()
+        .into_iter()
+        .map(|file| file.name.trim_start_matches('/').to_owned())
+        .collect();
+    live_files.sort_unstable();
+    assert_eq!(files, live_files);
+    assert_eq!(files.len(), 3);
+    for file in &files {
+        assert!((&cp_path).as_ref().join(file).exists(), "{file}");
+    }
+}
@@ -14,6 +14,11 @@
 
 mod util;
 
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
 use pretty_assertions::assert_eq;
 
 use rust_rocksdb::{CompactionDecision, Options, DB};
@@ -47,3 +52,41 @@ fn compaction_filter_test() {
         assert_eq!(&*db.get(b"%k").unwrap().unwrap(), b"secret");
     }
 }
+
+#[test]
+fn compaction_filter_sees_blob_values_test() {
+    let path = DBPath::new("_rust_rocksdb_filter_blob_values_test");
+    let seen_bytes = Arc::new(AtomicUsize::new(0));
+    let seen_bytes_in_filter = seen_bytes.clone();
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_enable_blob_files(true);
+    opts.set_min_blob_size(64);
+    opts.set_compaction_filter(
+        "blob_filter",
+        move |_level: u32, _key: &[u8], value: &[u8]| {
+            seen_bytes_in_filter.fetch_add(value.len(), Ordering::SeqCst);
+            if value.starts_with(b"remove") {
+                CompactionDecision::Remove
+            } else {
+                CompactionDecision::Keep
+            }
+        },
+    );
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        let keep = [b'k'; 1024];
+        let mut remove = [b'r'; 1024];
+        remove[..6].copy_from_slice(b"remove");
+        db.put(b"k1", keep).unwrap();
+        db.put(b"k2", remove).unwrap();
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), &keep[..]);
+        assert!(db.get(b"k2").unwrap().is_none());
+        // The filter must have observed both full values, not blob references.
+        assert_eq!(seen_bytes.load(Ordering::SeqCst), 2 * 1024);
+    }
+}
@@ -1,6 +1,6 @@
 mod util;
 
-use rust_rocksdb::{CompactOptions, Options, ReadOptions, DB};
+use rust_rocksdb::{ColumnFamilyDescriptor, CompactOptions, Comparator, Options, ReadOptions, DB};
 use std::cmp::Ordering;
 use std::iter::FromIterator;
 use util::{U64Comparator, U64Timestamp};
@@ -273,3 +273,77 @@ fn test_comparator_with_column_family_with_ts() {
 
     let _ = DB::destroy(&Options::default(), path);
 }
+
+#[test]
+fn test_shared_comparator_handle() {
+    let tempdir = tempfile::Builder::new()
+        .prefix("_rust_rocksdb_shared_comparator_handle")
+        .tempdir()
+        .expect("Failed to create temporary path for the _rust_rocksdb_shared_comparator_handle");
+    let path = tempdir.path();
+    let cf_names = ["cf1", "cf2", "cf3"];
+    let comparator = Comparator::new("reverse", Box::new(|a: &[u8], b: &[u8]| b.cmp(a)));
+
+    let cf_descriptors = || {
+        cf_names.map(|name| {
+            let mut cf_opts = Options::default();
+            cf_opts.set_comparator_handle(&comparator);
+            ColumnFamilyDescriptor::new(name, cf_opts)
+        })
+    };
+    let mut db_opts = Options::default();
+    db_opts.create_missing_column_families(true);
+    db_opts.create_if_missing(true);
+
+    for reopen in [false, true] {
+        let db = DB::open_cf_descriptors(&db_opts, path, cf_descriptors()).unwrap();
+        for name in cf_names {
+            let cf = db.cf_handle(name).unwrap();
+            if !reopen {
+                for key in ["a", "c", "b"] {
+                    db.put_cf(&cf, key, name).unwrap();
+                }
+            }
+            let keys: Vec<_> = db
+                .iterator_cf(&cf, rust_rocksdb::IteratorMode::Start)
+                .map(|item| item.unwrap().0.into_vec())
+                .collect();
+            assert_eq!(keys, [b"c", b"b", b"a"].map(|key| key.to_vec()));
+        }
+    }
+}
+
+#[test]
+fn test_comparator_handle_outlives_create_cf_options() {
+    let tempdir = tempfile::Builder::new()
+        .prefix("_rust_rocksdb_comparator_handle_create_cf")
+        .tempdir()
+        .expect(
+            "Failed to create temporary path for the _rust_rocksdb_comparator_handle_create_cf",
+        );
+    let path = tempdir.path();
+    let mut db_opts = Options::default();
+    db_opts.create_if_missing(true);
+    let mut db = DB::open(&db_opts, path).unwrap();
+
+    {
+        let comparator = Comparator::new("reverse", Box::new(|a: &[u8], b: &[u8]| b.cmp(a)));
+        let mut cf_opts = Options::default();
+        cf_opts.set_comparator_handle(&comparator);
+        db.create_cf("cf1", &cf_opts).unwrap();
+        // both the options and the comparator are dropped here; the DB must
+        // keep the comparator alive
+    }
+
+    let cf = db.cf_handle("cf1").unwrap();
+    for key in ["a", "c", "b"] {
+        db.put_cf(&cf, key, key).unwrap();
+    }
+    db.flush_cf(&cf).unwrap();
+    assert_eq!(db.get_cf(&cf, "b").unwrap().unwrap(), b"b");
+    let keys: Vec<_> = db
+        .iterator_cf(&cf, rust_rocksdb::IteratorMode::Start)
+        .map(|item| item.unwrap().0.into_vec())
+        .collect();
+    assert_eq!(keys, [b"c", b"b", b"a"].map(|key| key.to_vec()));
+}
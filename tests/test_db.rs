@@ -20,14 +20,16 @@ use std::{sync::Arc, thread, time::Duration};
 
 use pretty_assertions::assert_eq;
 
+use rust_rocksdb::perf::{set_perf_stats, PerfStatsLevel};
 use rust_rocksdb::statistics::{Histogram, StatsLevel, Ticker};
 use rust_rocksdb::{
     perf::get_memory_usage_stats, BlockBasedOptions, BottommostLevelCompaction, Cache,
     ColumnFamilyDescriptor, ColumnFamilyTtl, CompactOptions, CuckooTableOptions, DBAccess,
-    DBCompactionStyle, DBWithThreadMode, Env, Error, ErrorKind, FifoCompactOptions, IteratorMode,
-    MultiThreaded, Options, PerfContext, PerfMetric, RateLimiterMode, ReadOptions, SingleThreaded,
-    SliceTransform, Snapshot, UniversalCompactOptions, UniversalCompactionStopStyle,
-    WaitForCompactOptions, WriteBatch, DB, DEFAULT_COLUMN_FAMILY_NAME,
+    DBCompactionStyle, DBWithThreadMode, Env, Error, ErrorKind, FifoCompactOptions,
+    GetIntoBufferResult, IteratorMode, MultiThreaded, Options, PerfContext, PerfMetric,
+    RateLimiterMode, ReadOptions, ReadTier, SingleThreaded, SizeApproximationFlags, SliceTransform,
+    Snapshot, UniversalCompactOptions, UniversalCompactionStopStyle, WaitForCompactOptions,
+    WriteBatch, DB, DEFAULT_COLUMN_FAMILY_NAME,
 };
 use util::{assert_iter, pair, DBPath, U64Comparator, U64Timestamp};
 
@@ -203,6 +205,34 @@ fn iterator_test_tailing() {
     }
 }
 
+#[test]
+fn iterator_test_tailing_sees_new_writes() {
+    let path = DBPath::new("_rust_rocksdb_iteratortest_tailing_new_writes");
+    let db = DB::open_default(&path).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+
+    let mut ro = ReadOptions::default();
+    ro.set_tailing(true);
+    let mut iter = db.raw_iterator_opt(ro);
+    iter.seek_to_first();
+    assert_eq!(iter.key(), Some(b"k1".as_ref()));
+
+    db.put(b"k2", b"v2").unwrap();
+    iter.next();
+    assert!(iter.valid());
+    assert_eq!(iter.key(), Some(b"k2".as_ref()));
+    assert_eq!(iter.value(), Some(b"v2".as_ref()));
+
+    iter.next();
+    assert!(!iter.valid());
+    iter.status().unwrap();
+
+    // After running past the end, seeking again picks up newer writes.
+    db.put(b"k3", b"v3").unwrap();
+    iter.seek(b"k3");
+    assert_eq!(iter.key(), Some(b"k3".as_ref()));
+}
+
 #[test]
 fn iterator_test_upper_bound() {
     let path = DBPath::new("_rust_rocksdb_iteratortest_upper_bound");
@@ -263,6 +293,48 @@ fn snapshot_test() {
     }
 }
 
+#[test]
+fn snapshot_repeatable_read_test() {
+    let path = DBPath::new("_rust_rocksdb_snapshot_repeatable_read");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+    db.put(b"k1", b"old").unwrap();
+    db.put_cf(&cf1, b"k1", b"old").unwrap();
+
+    let snapshot = db.snapshot();
+    db.put(b"k1", b"new").unwrap();
+    db.put(b"k2", b"new").unwrap();
+    db.put_cf(&cf1, b"k1", b"new").unwrap();
+
+    assert_eq!(snapshot.get(b"k1").unwrap(), Some(b"old".to_vec()));
+    assert_eq!(snapshot.get_cf(&cf1, b"k1").unwrap(), Some(b"old".to_vec()));
+    let values: Vec<_> = snapshot
+        .multi_get([b"k1", b"k2"])
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(values, [Some(b"old".to_vec()), None]);
+    assert_iter(
+        snapshot.iterator(IteratorMode::Start),
+        &[pair(b"k1", b"old")],
+    );
+    // read options passed to the snapshot are bound to it
+    assert_eq!(
+        snapshot.get_opt(b"k1", ReadOptions::default()).unwrap(),
+        Some(b"old".to_vec())
+    );
+
+    assert_eq!(db.get(b"k1").unwrap(), Some(b"new".to_vec()));
+    assert_eq!(db.get_cf(&cf1, b"k1").unwrap(), Some(b"new".to_vec()));
+    assert_iter(
+        db.iterator(IteratorMode::Start),
+        &[pair(b"k1", b"new"), pair(b"k2", b"new")],
+    );
+}
+
 #[derive(Clone)]
 struct SnapshotWrapper<'db> {
     snapshot: Arc<Snapshot<'db>>,
@@ -338,6 +410,72 @@ fn set_option_test() {
     }
 }
 
+#[test]
+fn set_option_toggles_auto_compactions_test() {
+    let path = DBPath::new("_rust_rocksdb_set_options_toggles_auto_compactions");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_level_zero_file_num_compaction_trigger(2);
+        let db = DB::open(&opts, &path).unwrap();
+        let l0_files = |db: &DB| {
+            db.property_int_value(rust_rocksdb::properties::num_files_at_level(0))
+                .unwrap()
+                .unwrap()
+        };
+
+        db.set_options(&[("disable_auto_compactions", "true")])
+            .unwrap();
+        for i in 0..4 {
+            db.put(format!("k{i}"), b"v").unwrap();
+            db.flush().unwrap();
+        }
+        db.wait_for_compact(&WaitForCompactOptions::default())
+            .unwrap();
+        assert_eq!(l0_files(&db), 4);
+
+        db.set_options(&[("disable_auto_compactions", "false")])
+            .unwrap();
+        db.wait_for_compact(&WaitForCompactOptions::default())
+            .unwrap();
+        assert_eq!(l0_files(&db), 0);
+        for i in 0..4 {
+            assert_eq!(db.get(format!("k{i}")).unwrap().unwrap(), b"v");
+        }
+    }
+}
+
+#[test]
+fn get_options_test() {
+    let path = DBPath::new("_rust_rocksdb_get_options_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_write_buffer_size(8 * 1024 * 1024);
+        let mut db = DB::open(&opts, &path).unwrap();
+        let options = db.get_options().unwrap();
+        assert_eq!(options["write_buffer_size"], "8388608");
+        assert!(options["comparator"].contains("leveldb.BytewiseComparator"));
+
+        let mut cf_opts = Options::default();
+        cf_opts.set_write_buffer_size(4 * 1024 * 1024);
+        db.create_cf("cf1", &cf_opts).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        assert_eq!(
+            db.get_options_cf(&cf1).unwrap()["write_buffer_size"],
+            "4194304"
+        );
+
+        db.set_options(&[("write_buffer_size", "16777216")])
+            .unwrap();
+        assert_eq!(db.get_options().unwrap()["write_buffer_size"], "16777216");
+        assert_eq!(
+            db.get_options_cf(&cf1).unwrap()["write_buffer_size"],
+            "4194304"
+        );
+    }
+}
+
 #[test]
 fn set_option_cf_test() {
     let path = DBPath::new("_rust_rocksdb_set_options_cftest");
@@ -407,6 +545,85 @@ fn get_statistics_test() {
     }
 }
 
+#[test]
+fn max_subcompactions_test() {
+    let path = DBPath::new("_rust_rocksdb_max_subcompactions_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.enable_statistics();
+        opts.set_statistics_level(StatsLevel::All);
+        opts.set_max_subcompactions(4);
+        opts.set_max_background_jobs(8);
+        opts.set_disable_auto_compactions(true);
+        opts.set_target_file_size_base(64 << 10);
+        let db = DB::open(&opts, &path).unwrap();
+
+        // several overlapping L0 files give the compaction room to be split
+        for file in 0..4 {
+            for i in 0..5000 {
+                db.put(
+                    format!("key{i:05}"),
+                    format!("value{file}-{i:05}-{:0>64}", 0),
+                )
+                .unwrap();
+            }
+            db.flush().unwrap();
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        let histogram_data = opts.get_histogram_data(Histogram::NumSubcompactionsScheduled);
+        assert!(histogram_data.count() > 0);
+        assert!(histogram_data.max() > 1.0);
+        for i in (0..5000).step_by(499) {
+            assert_eq!(
+                db.get(format!("key{i:05}")).unwrap().unwrap(),
+                format!("value3-{i:05}-{:0>64}", 0).as_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn report_bg_io_stats_test() {
+    let path = DBPath::new("_rust_rocksdb_report_bg_io_stats_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.enable_statistics();
+        opts.set_statistics_level(StatsLevel::All);
+        opts.set_report_bg_io_stats(true);
+        opts.set_disable_auto_compactions(true);
+        let db = DB::open(&opts, &path).unwrap();
+
+        for file in 0..2 {
+            for i in 0..1000 {
+                db.put(format!("key{i:04}"), format!("value{file}"))
+                    .unwrap();
+            }
+            db.flush().unwrap();
+        }
+        assert_eq!(
+            opts.get_histogram_data(Histogram::FileWriteCompactionMicros)
+                .count(),
+            0
+        );
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        assert!(
+            opts.get_histogram_data(Histogram::FileWriteFlushMicros)
+                .count()
+                > 0
+        );
+        assert!(
+            opts.get_histogram_data(Histogram::FileWriteCompactionMicros)
+                .count()
+                > 0
+        );
+        assert_eq!(db.get(b"key0042").unwrap().unwrap(), b"value1");
+    }
+}
+
 #[test]
 fn set_column_family_metadata_test() {
     let path = DBPath::new("_set_column_family_metadata_test");
@@ -448,6 +665,34 @@ fn test_sequence_number() {
     }
 }
 
+#[test]
+fn sequence_number_change_detection_test() {
+    let path = DBPath::new("_rust_rocksdb_sequence_number_change_detection");
+    let db = DB::open_default(&path).unwrap();
+    db.put(b"k0", b"v0").unwrap();
+    let seen = db.latest_sequence_number();
+
+    db.get(b"k0").unwrap();
+    assert_eq!(db.latest_sequence_number(), seen);
+
+    let mut batch = WriteBatch::default();
+    batch.put(b"k1", b"v1");
+    batch.put(b"k2", b"v2");
+    batch.delete(b"k0");
+    db.write(batch).unwrap();
+    let latest = db.latest_sequence_number();
+    assert_eq!(latest, seen + 3);
+
+    let updates: Vec<_> = db
+        .get_updates_since(seen + 1)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].0, seen + 1);
+    assert_eq!(updates[0].1.len(), 3);
+}
+
 struct OperationCounts {
     puts: usize,
     deletes: usize,
@@ -534,6 +779,38 @@ fn test_get_updates_since_multiple_batches() {
     assert_eq!(counts.deletes, 0);
 }
 
+#[test]
+fn test_get_updates_since_archived_wal() {
+    let path = DBPath::new("_rust_rocksdb_test_get_updates_since_archived_wal");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_wal_ttl_seconds(3600);
+    opts.set_wal_size_limit_mb(64);
+    let db = DB::open(&opts, &path).unwrap();
+    db.put(b"key1", b"value1").unwrap();
+    db.put(b"key2", b"value2").unwrap();
+    // flushing retires the WAL holding both puts; it must be kept in the archive
+    db.flush().unwrap();
+    db.put(b"key3", b"value3").unwrap();
+
+    let db_path: &std::path::Path = (&path).as_ref();
+    let archived_wals = std::fs::read_dir(db_path.join("archive"))
+        .unwrap()
+        .filter(|entry| {
+            let path = entry.as_ref().unwrap().path();
+            path.extension().is_some_and(|ext| ext == "log")
+        })
+        .count();
+    assert!(archived_wals > 0);
+
+    let seqs = db
+        .get_updates_since(1)
+        .unwrap()
+        .map(|update| update.unwrap().0)
+        .collect::<Vec<_>>();
+    assert_eq!(seqs, vec![1, 2, 3]);
+}
+
 #[test]
 fn test_get_updates_since_one_batch() {
     let batches = [WriteBatch::default(), WriteBatch::with_capacity_bytes(13)];
@@ -563,6 +840,40 @@ fn test_get_updates_since_one_batch() {
     }
 }
 
+#[test]
+fn test_get_updates_since_log_data() {
+    let path = DBPath::new("_rust_rocksdb_test_get_updates_since_log_data");
+    let db = DB::open_default(&path).unwrap();
+    let annotation = b"txn-id:0123456789";
+
+    let mut batch = WriteBatch::default();
+    batch.put(b"key1", b"value1");
+    batch.put_log_data(annotation);
+    // log data is not an update
+    assert_eq!(batch.len(), 1);
+    db.write(batch).unwrap();
+    assert_eq!(db.latest_sequence_number(), 1);
+    assert_eq!(db.get(b"key1").unwrap().unwrap(), b"value1");
+
+    let mut iter = db.get_updates_since(0).unwrap();
+    let (seq, batch) = iter.next().unwrap().unwrap();
+    assert_eq!(seq, 1);
+    assert!(iter.next().is_none());
+
+    // the annotation travels through the WAL with the batch
+    assert!(batch
+        .data()
+        .windows(annotation.len())
+        .any(|window| window == annotation));
+    let mut counts = OperationCounts {
+        puts: 0,
+        deletes: 0,
+    };
+    batch.iterate(&mut counts);
+    assert_eq!(counts.puts, 1);
+    assert_eq!(counts.deletes, 0);
+}
+
 #[test]
 fn test_get_updates_since_batches() {
     let path = DBPath::new("_rust_rocksdb_test_get_updates_since_one_batch");
@@ -689,6 +1000,8 @@ fn test_open_with_ttl() {
     db.put(b"key1", b"value1").unwrap();
 
     thread::sleep(Duration::from_secs(2));
+    // Expired entries stay visible until a compaction drops them.
+    assert_eq!(db.get(b"key1").unwrap().unwrap(), b"value1");
     // Trigger a manual compaction, this will check the TTL filter
     // in the database and drop all expired entries.
     db.compact_range(None::<&[u8]>, None::<&[u8]>);
@@ -848,6 +1161,81 @@ fn compact_range_test() {
     }
 }
 
+#[test]
+fn exclusive_manual_compaction_test() {
+    let path = DBPath::new("_rust_rocksdb_exclusive_manual_compaction_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_disable_auto_compactions(true);
+        opts.set_max_subcompactions(2);
+        let db = DB::open(&opts, &path).unwrap();
+
+        for batch in 0..4 {
+            for i in 0..100 {
+                db.put(format!("key{i:03}"), format!("value{batch}"))
+                    .unwrap();
+            }
+            db.flush().unwrap();
+        }
+        assert_eq!(db.live_files().unwrap().len(), 4);
+
+        let mut compact_opts = CompactOptions::default();
+        compact_opts.set_exclusive_manual_compaction(true);
+        db.compact_range_opt(None::<&[u8]>, None::<&[u8]>, &compact_opts);
+
+        let files = db.live_files().unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].level, 6);
+        for i in 0..100 {
+            assert_eq!(db.get(format!("key{i:03}")).unwrap().unwrap(), b"value3");
+        }
+    }
+}
+
+#[test]
+fn universal_compaction_options_test() {
+    let path = DBPath::new("_rust_rocksdb_universal_compaction_options_test");
+    {
+        let mut uni_co_opts = UniversalCompactOptions::default();
+        uni_co_opts.set_size_ratio(10);
+        uni_co_opts.set_min_merge_width(2);
+        uni_co_opts.set_max_merge_width(4);
+        uni_co_opts.set_max_size_amplification_percent(100);
+        uni_co_opts.set_compression_size_percent(-1);
+        uni_co_opts.set_stop_style(UniversalCompactionStopStyle::Similar);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cf_opts = Options::default();
+        cf_opts.set_compaction_style(DBCompactionStyle::Universal);
+        cf_opts.set_universal_compaction_options(&uni_co_opts);
+        cf_opts.set_level_zero_file_num_compaction_trigger(2);
+        let db = DB::open_cf_with_opts(&opts, &path, [("cf1", cf_opts)]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        for file in 0..8 {
+            for i in 0..100 {
+                db.put_cf(&cf1, format!("key{i:03}"), format!("value{file}"))
+                    .unwrap();
+            }
+            db.flush_cf(&cf1).unwrap();
+        }
+        db.wait_for_compact(&WaitForCompactOptions::default())
+            .unwrap();
+
+        // background compactions merged the sorted runs
+        assert!(db.live_files().unwrap().len() < 8);
+        for i in 0..100 {
+            assert_eq!(
+                db.get_cf(&cf1, format!("key{i:03}")).unwrap().unwrap(),
+                b"value7"
+            );
+        }
+    }
+}
+
 #[test]
 fn fifo_compaction_test() {
     let path = DBPath::new("_rust_rocksdb_fifo_compaction_test");
@@ -900,6 +1288,111 @@ fn fifo_compaction_test() {
     }
 }
 
+#[test]
+fn get_pinned_with_stats_test() {
+    let path = DBPath::new("_rust_rocksdb_get_pinned_with_stats_test");
+    {
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_bloom_filter(10.0, false);
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_block_based_table_factory(&block_opts);
+        let db = DB::open(&opts, &path).unwrap();
+        for i in (0..200).step_by(2) {
+            db.put(format!("k{i:03}"), b"value").unwrap();
+        }
+        db.flush().unwrap();
+
+        let readopts = ReadOptions::default();
+        set_perf_stats(PerfStatsLevel::EnableCount);
+        let (present, present_stats) = db.get_pinned_with_stats(b"k042", &readopts);
+        let (absent, absent_stats) = db.get_pinned_with_stats(b"k043", &readopts);
+        set_perf_stats(PerfStatsLevel::Disable);
+
+        assert_eq!(present.unwrap().unwrap().as_ref(), b"value");
+        assert_eq!(present_stats.metric(PerfMetric::BloomSstHitCount), 1);
+        assert_eq!(present_stats.metric(PerfMetric::BloomSstMissCount), 0);
+        assert!(present_stats.metric(PerfMetric::BlockReadCount) > 0);
+
+        // the bloom filter rules the key out without reading a data block
+        assert!(absent.unwrap().is_none());
+        assert_eq!(absent_stats.metric(PerfMetric::BloomSstHitCount), 0);
+        assert_eq!(absent_stats.metric(PerfMetric::BloomSstMissCount), 1);
+    }
+}
+
+#[test]
+fn fifo_compaction_drops_oldest_files_test() {
+    let path = DBPath::new("_rust_rocksdb_fifo_compaction_drops_oldest_files_test");
+    {
+        let mut fifo_co_opts = FifoCompactOptions::default();
+        fifo_co_opts.set_max_table_files_size(64 << 10); // 64KB
+        fifo_co_opts.set_allow_compaction(true);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compaction_style(DBCompactionStyle::Fifo);
+        opts.set_fifo_compaction_options(&fifo_co_opts);
+        opts.set_compression_type(rust_rocksdb::DBCompressionType::None);
+        let db = DB::open(&opts, &path).unwrap();
+
+        // every flush produces a ~20KB file
+        for file in 0..20 {
+            for i in 0..20 {
+                db.put(format!("key{file:02}-{i:02}"), [b'v'; 1024])
+                    .unwrap();
+            }
+            db.flush().unwrap();
+            db.wait_for_compact(&WaitForCompactOptions::default())
+                .unwrap();
+        }
+
+        let total_size: usize = db.live_files().unwrap().iter().map(|f| f.size).sum();
+        assert!(total_size <= 128 << 10, "total size: {total_size}");
+        let num_keys = db
+            .property_int_value(rust_rocksdb::properties::ESTIMATE_NUM_KEYS)
+            .unwrap()
+            .unwrap();
+        assert!(num_keys < 400, "num keys: {num_keys}");
+        // the oldest data was dropped while the newest is kept
+        assert!(db.get(b"key00-00").unwrap().is_none());
+        assert!(db.get(b"key19-19").unwrap().is_some());
+    }
+}
+
+#[test]
+fn perf_context_snapshot_test() {
+    let path = DBPath::new("_rust_rocksdb_perf_context_snapshot_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for i in 0..10 {
+            db.put(format!("k{i}"), b"old").unwrap();
+            db.put(format!("k{i}"), b"new").unwrap();
+        }
+
+        set_perf_stats(PerfStatsLevel::EnableCount);
+        let ctx = PerfContext::default();
+        let before = ctx.snapshot();
+        assert_eq!(db.iterator(IteratorMode::Start).count(), 10);
+        let after = ctx.snapshot();
+        set_perf_stats(PerfStatsLevel::Disable);
+
+        let diff = after.diff(&before);
+        assert!(diff.metric(PerfMetric::InternalKeySkippedCount) > 0);
+        assert_eq!(
+            diff.metric(PerfMetric::InternalKeySkippedCount),
+            after.metric(PerfMetric::InternalKeySkippedCount)
+                - before.metric(PerfMetric::InternalKeySkippedCount)
+        );
+        assert_eq!(
+            before
+                .diff(&after)
+                .metric(PerfMetric::InternalKeySkippedCount),
+            0
+        );
+    }
+}
+
 #[test]
 fn wait_for_compact_test() {
     let path = DBPath::new("_rust_rocksdb_wait_for_compact_test");
@@ -962,6 +1455,44 @@ fn env_and_dbpaths_test() {
     }
 }
 
+#[test]
+fn shared_env_shutdown_test() {
+    let path1 = DBPath::new("_rust_rocksdb_shared_env_shutdown_test_1");
+    let path2 = DBPath::new("_rust_rocksdb_shared_env_shutdown_test_2");
+    {
+        let mut env = Env::new().unwrap();
+        env.set_background_threads(2);
+        env.set_high_priority_background_threads(1);
+        env.lower_thread_pool_io_priority();
+        env.lower_high_priority_thread_pool_io_priority();
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_env(&env);
+        let dbs = [
+            DB::open(&opts, &path1).unwrap(),
+            DB::open(&opts, &path2).unwrap(),
+        ];
+        drop(opts);
+
+        for db in &dbs {
+            for i in 0..100 {
+                db.put(format!("key{i:03}"), b"value").unwrap();
+            }
+            db.flush().unwrap();
+            db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        }
+
+        // drain the shared thread pools before shutting down
+        for db in &dbs {
+            db.cancel_all_background_work(true);
+            assert_eq!(db.get(b"key042").unwrap().unwrap(), b"value");
+        }
+        drop(dbs);
+        env.join_all_threads();
+    }
+}
+
 #[test]
 fn prefix_extract_and_iterate_test() {
     let path = DBPath::new("_rust_rocksdb_prefix_extract_and_iterate");
@@ -1362,30 +1893,156 @@ fn delete_range_test() {
 }
 
 #[test]
-fn multi_get() {
-    let path = DBPath::new("_rust_rocksdb_multi_get");
+fn write_batch_delete_range_test() {
+    let path = DBPath::new("_rust_rocksdb_write_batch_delete_range_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        for key in ["a/1", "a/2", "a/3", "b/1"] {
+            db.put(key, "old").unwrap();
+            db.put_cf(&cf1, key, "old").unwrap();
+        }
+
+        // atomically replace everything under the "a/" prefix, the put lands
+        // after the range deletion so it is not covered by it
+        let mut batch = WriteBatch::default();
+        batch.delete_range(b"a/", b"a0");
+        batch.put(b"a/2", b"new");
+        batch.delete_range_cf(&cf1, b"a/", b"a0");
+        batch.put_cf(&cf1, b"a/4", b"new");
+        db.write(batch).unwrap();
+
+        let contents = |iter: rust_rocksdb::DBIterator| {
+            iter.map(|item| {
+                let (key, value) = item.unwrap();
+                (
+                    String::from_utf8(key.to_vec()).unwrap(),
+                    String::from_utf8(value.to_vec()).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            contents(db.iterator(IteratorMode::Start)),
+            [
+                ("a/2".to_owned(), "new".to_owned()),
+                ("b/1".to_owned(), "old".to_owned())
+            ]
+        );
+        assert_eq!(
+            contents(db.iterator_cf(&cf1, IteratorMode::Start)),
+            [
+                ("a/4".to_owned(), "new".to_owned()),
+                ("b/1".to_owned(), "old".to_owned())
+            ]
+        );
+    }
+}
 
+#[test]
+fn read_tier_test() {
+    let path = DBPath::new("_rust_rocksdb_read_tier_test");
     {
         let db = DB::open_default(&path).unwrap();
-        let initial_snap = db.snapshot();
         db.put(b"k1", b"v1").unwrap();
-        let k1_snap = db.snapshot();
+        db.flush().unwrap();
         db.put(b"k2", b"v2").unwrap();
 
-        let _ = db.multi_get([b"k0"; 40]);
-
-        let assert_values = |values: Vec<_>| {
-            assert_eq!(3, values.len());
-            assert_eq!(values[0], None);
-            assert_eq!(values[1], Some(b"v1".to_vec()));
-            assert_eq!(values[2], Some(b"v2".to_vec()));
+        let tier = |tier: ReadTier| {
+            let mut readopts = ReadOptions::default();
+            readopts.set_read_tier(tier);
+            readopts
         };
 
-        let values = db
-            .multi_get([b"k0", b"k1", b"k2"])
-            .into_iter()
-            .map(Result::unwrap)
-            .collect::<Vec<_>>();
+        // k1 only lives on disk, so a cache-only read must not block on IO
+        let err = db.get_opt(b"k1", &tier(ReadTier::BlockCache)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Incomplete);
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        // the regular read has loaded the block into the block cache
+        assert_eq!(
+            db.get_opt(b"k1", &tier(ReadTier::BlockCache))
+                .unwrap()
+                .unwrap(),
+            b"v1"
+        );
+        assert_eq!(
+            db.get_opt(b"k2", &tier(ReadTier::BlockCache))
+                .unwrap()
+                .unwrap(),
+            b"v2"
+        );
+
+        // a memtable-only iterator doesn't see the flushed key
+        let keys: Vec<_> = db
+            .iterator_opt(IteratorMode::Start, tier(ReadTier::Memtable))
+            .map(|item| item.unwrap().0)
+            .collect();
+        assert_eq!(keys, vec![Box::from(&b"k2"[..])]);
+    }
+}
+
+#[test]
+fn ignore_range_deletions_test() {
+    let path = DBPath::new("_rust_rocksdb_ignore_range_deletions_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        for i in 1..=5 {
+            db.put_cf(&cf1, format!("k{i}"), format!("v{i}")).unwrap();
+        }
+        db.delete_range_cf(&cf1, b"k2", b"k4").unwrap();
+
+        let mut ignoring = ReadOptions::default();
+        ignoring.set_ignore_range_deletions(true);
+
+        // the shadowed keys are still physically present in the memtable
+        assert!(db.get_cf(&cf1, b"k2").unwrap().is_none());
+        assert_eq!(
+            db.get_cf_opt(&cf1, b"k2", &ignoring).unwrap().unwrap(),
+            b"v2"
+        );
+
+        let keys = |readopts: ReadOptions| {
+            db.iterator_cf_opt(&cf1, readopts, IteratorMode::Start)
+                .map(|item| String::from_utf8(item.unwrap().0.to_vec()).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(keys(ReadOptions::default()), ["k1", "k4", "k5"]);
+        assert_eq!(keys(ignoring), ["k1", "k2", "k3", "k4", "k5"]);
+    }
+}
+
+#[test]
+fn multi_get() {
+    let path = DBPath::new("_rust_rocksdb_multi_get");
+
+    {
+        let db = DB::open_default(&path).unwrap();
+        let initial_snap = db.snapshot();
+        db.put(b"k1", b"v1").unwrap();
+        let k1_snap = db.snapshot();
+        db.put(b"k2", b"v2").unwrap();
+
+        let _ = db.multi_get([b"k0"; 40]);
+
+        let assert_values = |values: Vec<_>| {
+            assert_eq!(3, values.len());
+            assert_eq!(values[0], None);
+            assert_eq!(values[1], Some(b"v1".to_vec()));
+            assert_eq!(values[2], Some(b"v2".to_vec()));
+        };
+
+        let values = db
+            .multi_get([b"k0", b"k1", b"k2"])
+            .into_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
 
         assert_values(values);
 
@@ -1423,6 +2080,32 @@ fn multi_get() {
     }
 }
 
+#[test]
+fn multi_get_per_key_errors() {
+    let path = DBPath::new("_rust_rocksdb_multi_get_per_key_errors");
+
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        // k1 lives in an SST whose data block isn't cached, so a cache-only
+        // read fails for that key alone; k9 is past the SST's key range
+        let mut readopts = ReadOptions::default();
+        readopts.set_read_tier(ReadTier::BlockCache);
+        let mut values = db
+            .multi_get_opt([b"k1", b"k2", b"k9"], &readopts)
+            .into_iter();
+
+        let err = values.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Incomplete);
+        assert_eq!(values.next().unwrap().unwrap(), Some(b"v2".to_vec()));
+        assert_eq!(values.next().unwrap().unwrap(), None);
+        assert!(values.next().is_none());
+    }
+}
+
 #[test]
 fn multi_get_cf() {
     let path = DBPath::new("_rust_rocksdb_multi_get_cf");
@@ -1453,6 +2136,38 @@ fn multi_get_cf() {
     }
 }
 
+#[test]
+fn multi_get_cf_labeled() {
+    let path = DBPath::new("_rust_rocksdb_multi_get_cf_labeled");
+
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1", "cf2"]).unwrap();
+
+        let cf1 = db.cf_handle("cf1").unwrap();
+        db.put_cf(&cf1, b"k1", b"v1").unwrap();
+
+        let cf2 = db.cf_handle("cf2").unwrap();
+        db.put_cf(&cf2, b"k2", b"v2").unwrap();
+
+        let values = db.multi_get_cf_labeled(vec![(&cf2, b"k2"), (&cf1, b"k2"), (&cf1, b"k1")]);
+        let values = values
+            .into_iter()
+            .map(|(cf_name, key, value)| (cf_name, key, value.unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                ("cf2".to_owned(), b"k2", Some(b"v2".to_vec())),
+                ("cf1".to_owned(), b"k2", None),
+                ("cf1".to_owned(), b"k1", Some(b"v1".to_vec())),
+            ]
+        );
+    }
+}
+
 #[test]
 fn batched_multi_get_cf() {
     let path = DBPath::new("_rust_rocksdb_batched_multi_get_cf");
@@ -1480,6 +2195,81 @@ fn batched_multi_get_cf() {
     }
 }
 
+#[test]
+fn batched_multi_get_multi_cf() {
+    let path = DBPath::new("_rust_rocksdb_batched_multi_get_multi_cf");
+
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1", "cf2"]).unwrap();
+
+        let cf1 = db.cf_handle("cf1").unwrap();
+        db.put_cf(&cf1, b"k1", b"v1").unwrap();
+        db.put_cf(&cf1, b"k3", b"v3").unwrap();
+        let cf2 = db.cf_handle("cf2").unwrap();
+        db.put_cf(&cf2, b"k1", b"w1").unwrap();
+
+        let values = db.batched_multi_get_multi_cf(
+            vec![
+                (&cf1, vec![&b"k1"[..], b"k2", b"k3"]),
+                (&cf2, vec![&b"k1"[..], b"k3"]),
+            ],
+            true, // sorted_input
+        );
+        let values = values
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|value| value.unwrap().map(|value| value.to_vec()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                vec![Some(b"v1".to_vec()), None, Some(b"v3".to_vec())],
+                vec![Some(b"w1".to_vec()), None],
+            ]
+        );
+    }
+}
+
+#[test]
+fn multi_get_pinned_into() {
+    let path = DBPath::new("_rust_rocksdb_multi_get_pinned_into");
+
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        db.put(b"k3", b"v3").unwrap();
+
+        let mut out = Vec::with_capacity(8);
+        let capacity = out.capacity();
+
+        db.multi_get_pinned_into([b"k0", b"k1", b"k2"], false, &mut out);
+        let values: Vec<_> = out
+            .iter()
+            .map(|value| value.as_ref().unwrap().as_ref().map(|v| v.to_vec()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![None, Some(b"v1".to_vec()), Some(b"v2".to_vec())]
+        );
+
+        db.multi_get_pinned_into([b"k3", b"k4"], true, &mut out);
+        let values: Vec<_> = out
+            .iter()
+            .map(|value| value.as_ref().unwrap().as_ref().map(|v| v.to_vec()))
+            .collect();
+        assert_eq!(values, vec![Some(b"v3".to_vec()), None]);
+        assert_eq!(out.capacity(), capacity);
+    }
+}
+
 #[test]
 fn key_may_exist() {
     let path = DBPath::new("_rust_key_may_exist");
@@ -1507,6 +2297,42 @@ fn key_may_exist_cf() {
     }
 }
 
+#[test]
+fn multi_key_may_exist_cf() {
+    let path = DBPath::new("_rust_multi_key_may_exist_cf");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut cf_opts = Options::default();
+    let mut block_opts = BlockBasedOptions::default();
+    block_opts.set_bloom_filter(10.0, false);
+    cf_opts.set_block_based_table_factory(&block_opts);
+    let db = DB::open_cf_descriptors(
+        &opts,
+        &path,
+        vec![ColumnFamilyDescriptor::new("cf", cf_opts)],
+    )
+    .unwrap();
+    let cf = db.cf_handle("cf").unwrap();
+
+    for i in 0..50 {
+        db.put_cf(&cf, format!("k{i:02}"), "v").unwrap();
+    }
+    db.flush_cf(&cf).unwrap();
+    db.put_cf(&cf, "m1", "v").unwrap();
+
+    // Absent keys outside the SST's key range are ruled out without
+    // consulting its bloom filter.
+    let keys = ["k10", "a", "m1", "l5", "z", "k49"];
+    assert_eq!(
+        db.multi_key_may_exist_cf(&cf, keys, &ReadOptions::default()),
+        [true, false, true, false, false, true]
+    );
+    assert!(db
+        .multi_key_may_exist_cf(&cf, Vec::<&[u8]>::new(), &ReadOptions::default())
+        .is_empty());
+}
+
 #[test]
 fn key_may_exist_cf_value() {
     let path = DBPath::new("_rust_key_may_exist_cf_value");
@@ -1648,6 +2474,90 @@ fn test_atomic_flush_cfs() {
     }
 }
 
+#[test]
+fn flush_cfs_subset_test() {
+    let n = DBPath::new("_rust_rocksdb_flush_cfs_subset");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_atomic_flush(true);
+    {
+        let db = DB::open_cf(&opts, &n, ["cf1", "cf2", "cf3"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        let cf2 = db.cf_handle("cf2").unwrap();
+        let cf3 = db.cf_handle("cf3").unwrap();
+
+        let mut write_options = rust_rocksdb::WriteOptions::new();
+        write_options.disable_wal(true);
+        db.put_cf_opt(&cf1, "k1", "v1", &write_options).unwrap();
+        db.put_cf_opt(&cf2, "k2", "v2", &write_options).unwrap();
+        db.put_cf_opt(&cf3, "k3", "v3", &write_options).unwrap();
+
+        db.flush_cfs(&[&cf1, &cf2]).unwrap();
+
+        let mem_entries = |cf| {
+            db.property_int_value_cf(cf, rust_rocksdb::properties::NUM_ENTRIES_ACTIVE_MEM_TABLE)
+                .unwrap()
+        };
+        assert_eq!(mem_entries(&cf1), Some(0));
+        assert_eq!(mem_entries(&cf2), Some(0));
+        // cf3 was not part of the flush, so its write is still in the memtable.
+        assert_eq!(mem_entries(&cf3), Some(1));
+    }
+
+    let db = DB::open_cf(&opts, &n, ["cf1", "cf2", "cf3"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+    let cf2 = db.cf_handle("cf2").unwrap();
+    assert_eq!(db.get_cf(&cf1, "k1").unwrap(), Some(b"v1".to_vec()));
+    assert_eq!(db.get_cf(&cf2, "k2").unwrap(), Some(b"v2".to_vec()));
+}
+
+#[test]
+fn checkpoint_consistent_shutdown_test() {
+    let n = DBPath::new("_rust_rocksdb_checkpoint_consistent_shutdown");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_atomic_flush(true);
+    {
+        let db = DB::open_cf(&opts, &n, ["cf1", "cf2"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        let cf2 = db.cf_handle("cf2").unwrap();
+
+        for i in 0..100 {
+            let key = format!("k{i:03}");
+            db.put(&key, "default").unwrap();
+            db.put_cf(&cf1, &key, "cf1").unwrap();
+            db.put_cf(&cf2, &key, "cf2").unwrap();
+        }
+
+        db.checkpoint_consistent_shutdown(true).unwrap();
+
+        // every column family has been persisted to table files
+        let mut flushed: Vec<_> = db
+            .live_files()
+            .unwrap()
+            .into_iter()
+            .map(|f| f.column_family_name)
+            .collect();
+        flushed.sort();
+        flushed.dedup();
+        assert_eq!(flushed, vec!["cf1", "cf2", "default"]);
+    }
+    {
+        let db = DB::open_cf(&opts, &n, ["cf1", "cf2"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        let cf2 = db.cf_handle("cf2").unwrap();
+
+        for i in 0..100 {
+            let key = format!("k{i:03}");
+            assert_eq!(db.get(&key).unwrap().unwrap(), b"default");
+            assert_eq!(db.get_cf(&cf1, &key).unwrap().unwrap(), b"cf1");
+            assert_eq!(db.get_cf(&cf2, &key).unwrap().unwrap(), b"cf2");
+        }
+    }
+}
+
 #[test]
 fn ratelimiter_with_mode_test() {
     let path = DBPath::new("_rust_rocksdb_ratelimiter_with_mode_test");
@@ -1736,3 +2646,617 @@ fn test_full_history_ts_low() {
         let _ = DB::destroy(&Options::default(), &path);
     }
 }
+
+#[test]
+fn test_u64_timestamp_comparator() {
+    let path = DBPath::new("_rust_rocksdb_u64_timestamp_comparator");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+
+        let mut cf_opts = Options::default();
+        cf_opts.set_u64_timestamp_comparator();
+
+        let db = DB::open_cf_with_opts(&opts, &path, vec![("cf", cf_opts)]).unwrap();
+        let cf = db.cf_handle("cf").unwrap();
+
+        db.put_cf_with_ts(&cf, b"k1", 1u64.to_le_bytes(), b"v1")
+            .unwrap();
+        db.put_cf_with_ts(&cf, b"k1", 2u64.to_le_bytes(), b"v2")
+            .unwrap();
+        db.put_cf_with_ts(&cf, b"k2", 2u64.to_le_bytes(), b"v3")
+            .unwrap();
+        db.delete_cf_with_ts(&cf, b"k1", 3u64.to_le_bytes())
+            .unwrap();
+
+        let read_at = |ts: u64, key: &[u8]| {
+            let mut readopts = ReadOptions::default();
+            readopts.set_timestamp(ts.to_le_bytes());
+            db.get_cf_opt(&cf, key, &readopts).unwrap()
+        };
+        assert_eq!(read_at(0, b"k1"), None);
+        assert_eq!(read_at(1, b"k1"), Some(b"v1".to_vec()));
+        assert_eq!(read_at(2, b"k1"), Some(b"v2".to_vec()));
+        assert_eq!(read_at(3, b"k1"), None);
+        assert_eq!(read_at(1, b"k2"), None);
+        assert_eq!(read_at(3, b"k2"), Some(b"v3".to_vec()));
+
+        // the ordering survives a flush, where the comparator sorts the table
+        db.flush_cf(&cf).unwrap();
+        assert_eq!(read_at(1, b"k1"), Some(b"v1".to_vec()));
+        assert_eq!(read_at(2, b"k1"), Some(b"v2".to_vec()));
+        assert_eq!(read_at(3, b"k1"), None);
+    }
+}
+
+#[test]
+fn timestamp_size_mismatch_test() {
+    let path = DBPath::new("_rust_rocksdb_timestamp_size_mismatch");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut cf_opts = Options::default();
+    cf_opts.set_u64_timestamp_comparator();
+    let db = DB::open_cf_with_opts(&opts, &path, vec![("cf", cf_opts)]).unwrap();
+    let cf = db.cf_handle("cf").unwrap();
+
+    let assert_mismatch = |err: Error| {
+        assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+        let message = err.into_string();
+        assert!(message.contains("mismatch"), "{message}");
+    };
+    assert_mismatch(
+        db.put_cf_with_ts(&cf, b"k1", 1u32.to_le_bytes(), b"v1")
+            .unwrap_err(),
+    );
+    assert_mismatch(
+        db.delete_cf_with_ts(&cf, b"k1", 1u32.to_le_bytes())
+            .unwrap_err(),
+    );
+
+    let mut readopts = ReadOptions::default();
+    readopts.set_timestamp(1u32.to_le_bytes());
+    assert_mismatch(db.get_cf_opt(&cf, b"k1", &readopts).unwrap_err());
+
+    readopts.set_timestamp(1u64.to_le_bytes());
+    assert_eq!(db.get_cf_opt(&cf, b"k1", &readopts).unwrap(), None);
+}
+
+#[test]
+fn write_batch_timestamp_size_mismatch_test() {
+    let path = DBPath::new("_rust_rocksdb_write_batch_timestamp_size_mismatch");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut cf_opts = Options::default();
+    cf_opts.set_u64_timestamp_comparator();
+    let db = DB::open_cf_with_opts(&opts, &path, vec![("cf", cf_opts)]).unwrap();
+    let cf = db.cf_handle("cf").unwrap();
+
+    let mut batch = WriteBatch::default();
+    batch
+        .put_cf_with_ts(&cf, b"k1", 1u64.to_le_bytes(), b"v1")
+        .unwrap();
+    let err = batch
+        .put_cf_with_ts(&cf, b"k2", 1u32.to_le_bytes(), b"v2")
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+    assert!(err.as_ref().contains("expected 8 bytes, got 4"));
+    let err = batch
+        .delete_cf_with_ts(&cf, b"k1", 1u32.to_le_bytes())
+        .unwrap_err();
+    assert!(err.as_ref().contains("expected 8 bytes, got 4"));
+    assert_eq!(batch.len(), 1);
+
+    db.write(batch).unwrap();
+    let mut readopts = ReadOptions::default();
+    readopts.set_timestamp(1u64.to_le_bytes());
+    assert_eq!(
+        db.get_cf_opt(&cf, b"k1", &readopts).unwrap().unwrap(),
+        b"v1"
+    );
+    assert_eq!(db.get_cf_opt(&cf, b"k2", &readopts).unwrap(), None);
+}
+
+#[test]
+fn verify_checksum_test() {
+    let path = DBPath::new("_rust_rocksdb_verify_checksum_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_file_checksum_crc32c(true);
+    let mut readopts = ReadOptions::default();
+    readopts.fill_cache(false);
+
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..100 {
+            db.put(format!("key{i:03}"), format!("value{i:03}"))
+                .unwrap();
+        }
+        db.flush().unwrap();
+        assert!(db.verify_checksum(&readopts).is_ok());
+        assert!(db.verify_file_checksums(&readopts).is_ok());
+    }
+
+    // flip some bytes inside the first data block of the table file
+    let sst = std::fs::read_dir(&path)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|p| p.extension().is_some_and(|ext| ext == "sst"))
+        .unwrap();
+    let mut data = std::fs::read(&sst).unwrap();
+    for b in &mut data[16..32] {
+        *b ^= 0xff;
+    }
+    std::fs::write(&sst, data).unwrap();
+
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        let err = db.verify_checksum(&readopts).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Corruption);
+        let err = db.verify_file_checksums(&readopts).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Corruption);
+    }
+
+    // without a checksum factory there is nothing to compare against
+    {
+        let db = DB::open_default(&path).unwrap();
+        let err = db.verify_file_checksums(&readopts).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn c_allocated_results_test() {
+    let path = DBPath::new("_rust_rocksdb_c_allocated_results_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        // every call hands out a fresh C buffer that must be freed exactly once
+        let identity = db.get_db_identity().unwrap();
+        for _ in 0..100 {
+            assert_eq!(db.get_db_identity().unwrap(), identity);
+            assert!(db.get_options_cf(&cf1).is_ok());
+            assert_eq!(
+                db.multi_get([b"k1", b"k2"])
+                    .into_iter()
+                    .map(Result::unwrap)
+                    .collect::<Vec<_>>(),
+                [Some(b"v1".to_vec()), None]
+            );
+        }
+    }
+}
+
+#[test]
+fn live_files_key_range_test() {
+    let path = DBPath::new("_rust_rocksdb_live_files_key_range_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for key in [b"b1", b"b5", b"c3", b"d9"] {
+            db.put(key, b"value").unwrap();
+        }
+        db.flush().unwrap();
+
+        let livefiles = db.live_files().unwrap();
+        assert_eq!(livefiles.len(), 1);
+        let file = &livefiles[0];
+        assert_eq!(file.level, 0);
+        assert!(file.size > 0);
+        assert_eq!(file.num_entries, 4);
+
+        let start_key = file.start_key.as_deref().unwrap();
+        let end_key = file.end_key.as_deref().unwrap();
+        assert_eq!(start_key, b"b1");
+        assert_eq!(end_key, b"d9");
+        for key in [b"b1", b"b5", b"c3", b"d9"] {
+            assert!(start_key <= &key[..] && &key[..] <= end_key);
+        }
+    }
+}
+
+#[test]
+fn live_files_by_level_test() {
+    let path = DBPath::new("_rust_rocksdb_live_files_by_level_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        opts.set_disable_auto_compactions(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        for key in [b"k1", b"k2"] {
+            db.put(key, b"value").unwrap();
+            db.flush().unwrap();
+        }
+        db.put_cf(&cf1, b"k1", b"value").unwrap();
+        db.flush_cf(&cf1).unwrap();
+        db.compact_range_cf(&cf1, None::<&[u8]>, None::<&[u8]>);
+        db.put_cf(&cf1, b"k2", b"value").unwrap();
+        db.flush_cf(&cf1).unwrap();
+
+        let grouped = db.live_files_by_level().unwrap();
+        let counts: Vec<_> = grouped
+            .iter()
+            .map(|((cf, level), files)| (cf.as_str(), *level, files.len()))
+            .collect();
+        assert_eq!(counts, [("cf1", 0, 1), ("cf1", 6, 1), ("default", 0, 2)]);
+        for ((cf, level), files) in &grouped {
+            assert!(files
+                .iter()
+                .all(|f| &f.column_family_name == cf && f.level == *level));
+        }
+    }
+}
+
+#[test]
+fn put_many_test() {
+    let path = DBPath::new("_rust_rocksdb_put_many_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+
+        db.put_many((0..10_000).map(|i| (format!("key{i}"), format!("value{i}"))))
+            .unwrap();
+        for i in (0..10_000).step_by(997) {
+            assert_eq!(
+                db.get(format!("key{i}")).unwrap().unwrap(),
+                format!("value{i}").as_bytes()
+            );
+        }
+
+        let cf1 = db.cf_handle("cf1").unwrap();
+        db.put_many_cf(&cf1, [(b"k1", b"v1"), (b"k2", b"v2")])
+            .unwrap();
+        assert_eq!(db.get_cf(&cf1, b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(db.get_cf(&cf1, b"k2").unwrap().unwrap(), b"v2");
+        assert!(db.get(b"k1").unwrap().is_none());
+    }
+}
+
+#[test]
+fn delete_ranges_cf_test() {
+    let path = DBPath::new("_rust_rocksdb_delete_ranges_cf_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        db.put_many_cf(&cf1, (0..100).map(|i| (format!("k{i:02}"), b"v")))
+            .unwrap();
+        let seq = db.latest_sequence_number();
+        let ranges = [
+            ("k00", "k05"),
+            ("k20", "k25"),
+            ("k40", "k45"),
+            ("k60", "k65"),
+            ("k80", "k85"),
+        ];
+        db.delete_ranges_cf(&cf1, ranges).unwrap();
+        // all five range deletions share one write batch
+        let (_, batch) = db
+            .get_updates_since(seq + 1)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(batch.len(), 5);
+
+        for i in 0..100 {
+            let deleted = i % 20 < 5;
+            assert_eq!(
+                db.get_cf(&cf1, format!("k{i:02}")).unwrap().is_none(),
+                deleted,
+                "k{i:02}"
+            );
+        }
+    }
+}
+
+#[test]
+fn non_waiting_flush_test() {
+    let path = DBPath::new("_rust_rocksdb_non_waiting_flush_test");
+    {
+        let db = DB::open_default(&path).unwrap();
+        for i in 0..1000 {
+            db.put(format!("key{i:04}"), b"value").unwrap();
+        }
+
+        let mut flush_opts = rust_rocksdb::FlushOptions::new();
+        flush_opts.set_wait(false);
+        db.flush_opt(&flush_opts).unwrap();
+        // writes keep going while the flush runs in the background
+        db.put(b"key1000", b"value").unwrap();
+
+        db.wait_for_compact(&WaitForCompactOptions::default())
+            .unwrap();
+        assert_eq!(db.live_files().unwrap().len(), 1);
+        assert_eq!(db.get(b"key0042").unwrap().unwrap(), b"value");
+        assert_eq!(db.get(b"key1000").unwrap().unwrap(), b"value");
+    }
+}
+
+#[test]
+fn approximate_memtable_stats_test() {
+    let path = DBPath::new("_rust_rocksdb_approximate_memtable_stats_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        for i in 0..1000 {
+            db.put_cf(&cf1, format!("a{i:04}"), b"value").unwrap();
+        }
+        db.put_cf(&cf1, b"b0000", b"value").unwrap();
+
+        let (count, size) = db.get_approximate_memtable_stats_cf(&cf1, b"a", b"b");
+        assert!((500..=2000).contains(&count), "count: {count}");
+        assert!(size > 0);
+
+        let (count, size) = db.get_approximate_memtable_stats_cf(&cf1, b"c", b"d");
+        assert_eq!((count, size), (0, 0));
+
+        let (count, _) = db.get_approximate_memtable_stats(b"a", b"b");
+        assert_eq!(count, 0);
+
+        db.flush_cf(&cf1).unwrap();
+        let (count, _) = db.get_approximate_memtable_stats_cf(&cf1, b"a", b"b");
+        assert_eq!(count, 0);
+    }
+}
+
+#[test]
+fn approximate_sizes_flags_test() {
+    let path = DBPath::new("_rust_rocksdb_approximate_sizes_flags_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        for i in 0..1000 {
+            db.put_cf(&cf1, format!("a{i:04}"), b"value").unwrap();
+        }
+        let ranges: [(&[u8], &[u8]); 2] = [(b"a", b"b"), (b"c", b"d")];
+        let memtables = SizeApproximationFlags::INCLUDE_MEMTABLES;
+        let files = SizeApproximationFlags::INCLUDE_FILES;
+
+        // the data only lives in the memtable so far
+        let sizes = db
+            .get_approximate_sizes_cf_opt(&cf1, ranges, memtables)
+            .unwrap();
+        assert!(sizes[0] > 0);
+        assert_eq!(sizes[1], 0);
+        let sizes = db
+            .get_approximate_sizes_cf_opt(&cf1, ranges, files)
+            .unwrap();
+        assert_eq!(sizes, [0, 0]);
+
+        db.flush_cf(&cf1).unwrap();
+        let sizes = db
+            .get_approximate_sizes_cf_opt(&cf1, ranges, files)
+            .unwrap();
+        assert!(sizes[0] > 0);
+        assert_eq!(sizes[1], 0);
+        let both = db
+            .get_approximate_sizes_cf_opt(&cf1, ranges, memtables | files)
+            .unwrap();
+        assert_eq!(both, sizes);
+
+        assert!(db
+            .get_approximate_sizes_cf_opt(&cf1, ranges, SizeApproximationFlags::NONE)
+            .is_err());
+    }
+}
+
+#[test]
+fn suggest_split_keys_test() {
+    let path = DBPath::new("_rust_rocksdb_suggest_split_keys_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut cf_opts = Options::default();
+    cf_opts.set_compression_type(rust_rocksdb::DBCompressionType::None);
+    cf_opts.set_target_file_size_base(64 * 1024);
+    let db = DB::open_cf_descriptors(
+        &opts,
+        &path,
+        vec![ColumnFamilyDescriptor::new("cf1", cf_opts)],
+    )
+    .unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    assert!(db.suggest_split_keys(&cf1, 1024).unwrap().is_empty());
+    assert!(db.suggest_split_keys(&cf1, 0).is_err());
+
+    for i in 0..1000 {
+        db.put_cf(&cf1, format!("k{i:04}"), [b'x'; 1024]).unwrap();
+    }
+    db.compact_range_cf(&cf1, None::<&[u8]>, None::<&[u8]>);
+    let total = db.get_column_family_metadata_cf(&cf1).size;
+    assert!(db.live_files().unwrap().len() > 4);
+
+    let split_keys = db.suggest_split_keys(&cf1, total / 2).unwrap();
+    assert_eq!(split_keys.len(), 1, "{split_keys:?}");
+    let first_shard = db
+        .iterator_cf(&cf1, IteratorMode::Start)
+        .map(Result::unwrap)
+        .take_while(|(key, _)| key.as_ref() < split_keys[0].as_slice())
+        .count();
+    // the split falls on a file boundary close to the middle
+    assert!((400..=600).contains(&first_shard), "{first_shard}");
+
+    assert!(db.suggest_split_keys(&cf1, total * 2).unwrap().is_empty());
+    assert!(db.suggest_split_keys(&cf1, total / 4).unwrap().len() >= 3);
+}
+
+#[test]
+fn get_into_buffer_test() {
+    let path = DBPath::new("_rust_rocksdb_get_into_buffer_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        let mut buffer = [0u8; 8];
+
+        db.put(b"k1", b"v1").unwrap();
+        assert_eq!(
+            db.get_into_buffer(b"k1", &mut buffer).unwrap(),
+            GetIntoBufferResult::Found(2)
+        );
+        assert_eq!(&buffer[..2], b"v1");
+        assert_eq!(
+            db.get_into_buffer(b"missing", &mut buffer).unwrap(),
+            GetIntoBufferResult::NotFound
+        );
+
+        db.put_cf(&cf1, b"empty", b"").unwrap();
+        db.put_cf(&cf1, b"large", [b'x'; 16]).unwrap();
+        assert_eq!(
+            db.get_into_buffer_cf(&cf1, b"empty", &mut buffer).unwrap(),
+            GetIntoBufferResult::Found(0)
+        );
+        assert_eq!(
+            db.get_into_buffer_cf(&cf1, b"large", &mut buffer).unwrap(),
+            GetIntoBufferResult::BufferTooSmall(16)
+        );
+        assert_eq!(&buffer[..2], b"v1");
+
+        // reads through a snapshot ignore later writes
+        db.put_cf(&cf1, b"k2", b"old").unwrap();
+        let snapshot = db.snapshot();
+        db.put_cf(&cf1, b"k2", b"newer").unwrap();
+        let mut readopts = ReadOptions::default();
+        readopts.set_snapshot(&snapshot);
+        assert_eq!(
+            db.get_into_buffer_cf_opt(&cf1, b"k2", &mut buffer, &readopts)
+                .unwrap(),
+            GetIntoBufferResult::Found(3)
+        );
+        assert_eq!(&buffer[..3], b"old");
+    }
+}
+
+#[test]
+fn warm_up_cache_test() {
+    let path = DBPath::new("_rust_rocksdb_warm_up_cache_test");
+    let cache = Cache::new_lru_cache(64 << 20);
+    let mut block_opts = BlockBasedOptions::default();
+    block_opts.set_block_cache(&cache);
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_block_based_table_factory(&block_opts);
+
+    {
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        for i in 0..10_000 {
+            db.put_cf(&cf1, format!("key{i:05}"), [b'v'; 100]).unwrap();
+        }
+        db.flush_cf(&cf1).unwrap();
+    }
+
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+    let usage_before = cache.get_usage();
+    db.warm_up_cache_cf(&cf1, &ReadOptions::default()).unwrap();
+    // the whole table file, roughly 1MB of values, now lives in the cache
+    assert!(cache.get_usage() > usage_before + (512 << 10));
+    // the default column family holds no table files
+    let usage_before = cache.get_usage();
+    db.warm_up_cache(&ReadOptions::default()).unwrap();
+    assert_eq!(cache.get_usage(), usage_before);
+}
+
+#[test]
+fn write_stall_test() {
+    let path = DBPath::new("_rust_rocksdb_write_stall_test");
+    // Holding the gate blocks the L0 compaction inside the compaction filter,
+    // keeping the L0 file count at the slowdown trigger.
+    let gate = Arc::new(std::sync::Mutex::new(()));
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_level_zero_file_num_compaction_trigger(2);
+    opts.set_level_zero_slowdown_writes_trigger(2);
+    opts.set_level_zero_stop_writes_trigger(20);
+    let filter_gate = Arc::clone(&gate);
+    opts.set_compaction_filter("gate", move |_, _: &[u8], _: &[u8]| {
+        let _unused = filter_gate.lock().unwrap();
+        rust_rocksdb::compaction_filter::Decision::Keep
+    });
+
+    let db = DB::open(&opts, &path).unwrap();
+    assert!(!db.is_write_stalled().unwrap());
+
+    let guard = gate.lock().unwrap();
+    // The same key in both files, so that the compaction can't be a trivial
+    // move that skips the filter.
+    for _ in 0..2 {
+        db.put(b"key", b"value").unwrap();
+        db.flush().unwrap();
+    }
+    assert!(db.is_write_stalled().unwrap());
+    assert!(!db.is_write_stopped().unwrap());
+
+    drop(guard);
+    db.wait_for_compact(&WaitForCompactOptions::default())
+        .unwrap();
+    assert!(!db.is_write_stalled().unwrap());
+}
+
+#[test]
+fn truncate_cf_test() {
+    let path = DBPath::new("_rust_rocksdb_truncate_cf_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &path, ["cf1", "cf2"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+    let cf2 = db.cf_handle("cf2").unwrap();
+
+    // Truncating an empty column family is a no-op.
+    db.truncate_cf(&cf1, false).unwrap();
+
+    db.put_many_cf(&cf1, (0..100).map(|i| (format!("k{i:02}"), b"v")))
+        .unwrap();
+    db.flush_cf(&cf1).unwrap();
+    db.put_cf(&cf1, b"z", b"v").unwrap();
+    db.put_cf(&cf2, b"k00", b"v").unwrap();
+
+    db.truncate_cf(&cf1, true).unwrap();
+    assert_eq!(db.iterator_cf(&cf1, IteratorMode::Start).count(), 0);
+    assert_eq!(
+        db.live_files()
+            .unwrap()
+            .iter()
+            .filter(|f| f.column_family_name == "cf1")
+            .count(),
+        0
+    );
+    assert_eq!(db.get_cf(&cf2, b"k00").unwrap(), Some(b"v".to_vec()));
+
+    // The handle stays valid.
+    db.put_cf(&cf1, b"k00", b"new").unwrap();
+    assert_eq!(db.get_cf(&cf1, b"k00").unwrap(), Some(b"new".to_vec()));
+
+    // A single key is deleted too.
+    db.truncate_cf(&cf1, false).unwrap();
+    assert_eq!(db.iterator_cf(&cf1, IteratorMode::Start).count(), 0);
+}
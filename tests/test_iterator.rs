@@ -379,3 +379,146 @@ fn test_iter_range() {
         check(&db, b"b0", prefix(b"\xff"), 0..0);
     }
 }
+
+#[test]
+fn test_key_iterator_cf() {
+    let path = DBPath::new("_rust_rocksdb_key_iterator_cf_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    let value = vec![b'v'; 64 * 1024];
+    let keys: Vec<Box<[u8]>> = (0..50)
+        .map(|i| format!("key{i:02}").into_bytes().into_boxed_slice())
+        .collect();
+    for key in &keys {
+        db.put_cf(&cf1, key, &value).unwrap();
+    }
+    db.flush_cf(&cf1).unwrap();
+    // keys in the default column family must not show up
+    db.put(b"key99", b"default").unwrap();
+
+    let got: Vec<_> = db
+        .key_iterator_cf(&cf1, IteratorMode::Start)
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(got, keys);
+
+    let mut got: Vec<_> = db
+        .key_iterator_cf(&cf1, IteratorMode::End)
+        .map(Result::unwrap)
+        .collect();
+    got.reverse();
+    assert_eq!(got, keys);
+
+    let got: Vec<_> = db
+        .key_iterator_cf(&cf1, IteratorMode::From(b"key45", Direction::Forward))
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(got, &keys[45..]);
+
+    let got: Vec<_> = db
+        .key_iterator(IteratorMode::Start)
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(got, vec![Box::from(&b"key99"[..])]);
+}
+
+#[test]
+fn test_auto_prefix_mode() {
+    let path = DBPath::new("_rust_rocksdb_auto_prefix_mode_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_prefix_extractor(rust_rocksdb::SliceTransform::create_fixed_prefix(3));
+    let mut block_opts = rust_rocksdb::BlockBasedOptions::default();
+    block_opts.set_bloom_filter(10.0, false);
+    opts.set_block_based_table_factory(&block_opts);
+    let db = DB::open(&opts, &path).unwrap();
+
+    for key in [b"aaa1", b"aaa2", b"aab1", b"aac1", b"bbb1"] {
+        db.put(key, key).unwrap();
+    }
+    db.flush().unwrap();
+
+    let scan = |auto_prefix_mode: bool, lower: &[u8], upper: &[u8]| {
+        let mut ro = rust_rocksdb::ReadOptions::default();
+        if auto_prefix_mode {
+            ro.set_auto_prefix_mode(true);
+        } else {
+            ro.set_total_order_seek(true);
+        }
+        ro.set_iterate_upper_bound(upper);
+        db.iterator_opt(IteratorMode::From(lower, Direction::Forward), ro)
+            .map(|item| item.unwrap().0)
+            .collect::<Vec<_>>()
+    };
+
+    // a range within a single prefix
+    let total_order = scan(false, b"aaa", b"aab");
+    assert_eq!(
+        total_order,
+        vec![Box::from(&b"aaa1"[..]), Box::from(&b"aaa2"[..])]
+    );
+    assert_eq!(scan(true, b"aaa", b"aab"), total_order);
+
+    // a range spanning several prefixes
+    let total_order = scan(false, b"aaa", b"bbb");
+    assert_eq!(total_order.len(), 4);
+    assert_eq!(scan(true, b"aaa", b"bbb"), total_order);
+}
+
+#[test]
+fn test_iterator_with_timestamps() {
+    let path = DBPath::new("_rust_rocksdb_iterator_with_timestamps_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut cf_opts = Options::default();
+    cf_opts.set_u64_timestamp_comparator();
+    let db = DB::open_cf_with_opts(&opts, &path, vec![("cf", cf_opts)]).unwrap();
+    let cf = db.cf_handle("cf").unwrap();
+
+    db.put_cf_with_ts(&cf, b"k1", 1u64.to_le_bytes(), b"a")
+        .unwrap();
+    db.put_cf_with_ts(&cf, b"k1", 3u64.to_le_bytes(), b"b")
+        .unwrap();
+    db.put_cf_with_ts(&cf, b"k2", 2u64.to_le_bytes(), b"c")
+        .unwrap();
+
+    let scan_at = |ts: u64| {
+        let mut readopts = rust_rocksdb::ReadOptions::default();
+        readopts.set_timestamp(ts.to_le_bytes());
+        db.iterator_cf_opt(&cf, readopts, IteratorMode::Start)
+            .with_timestamps()
+            .map(|item| {
+                let (key, value, ts) = item.unwrap();
+                let ts = u64::from_le_bytes(ts.as_ref().try_into().unwrap());
+                (key.to_vec(), value.to_vec(), ts)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        scan_at(2),
+        vec![
+            (b"k1".to_vec(), b"a".to_vec(), 1),
+            (b"k2".to_vec(), b"c".to_vec(), 2)
+        ]
+    );
+    assert_eq!(
+        scan_at(5),
+        vec![
+            (b"k1".to_vec(), b"b".to_vec(), 3),
+            (b"k2".to_vec(), b"c".to_vec(), 2)
+        ]
+    );
+
+    // the raw iterator exposes the timestamp of the current entry as well
+    let mut readopts = rust_rocksdb::ReadOptions::default();
+    readopts.set_timestamp(5u64.to_le_bytes());
+    let mut iter = db.raw_iterator_cf_opt(&cf, readopts);
+    iter.seek_to_first();
+    assert_eq!(iter.timestamp(), Some(&3u64.to_le_bytes()[..]));
+}
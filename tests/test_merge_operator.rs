@@ -15,7 +15,10 @@
 mod util;
 
 use pretty_assertions::assert_eq;
-use rust_rocksdb::{merge_operator::MergeFn, DBCompactionStyle, MergeOperands, Options, DB};
+use rust_rocksdb::{
+    merge_operator::{self, MergeFn},
+    DBCompactionStyle, MergeOperands, Options, DB,
+};
 use serde::{Deserialize, Serialize};
 use util::DBPath;
 
@@ -349,3 +352,91 @@ fn test_merge_state() {
     }
     assert!(DB::destroy(&opts, path).is_ok());
 }
+
+#[test]
+fn counter_merge_operators_test() {
+    let path = DBPath::new("_rust_rocksdb_counter_merge_operators_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut signed_opts = Options::default();
+    signed_opts.set_merge_operator_associative("i64_add", merge_operator::i64_add());
+    opts.set_merge_operator_associative("u64_add", merge_operator::u64_add());
+
+    let db = DB::open_cf_descriptors(
+        &opts,
+        &path,
+        vec![rust_rocksdb::ColumnFamilyDescriptor::new(
+            "signed",
+            signed_opts,
+        )],
+    )
+    .unwrap();
+    let get_u64 =
+        |key: &[u8]| u64::from_le_bytes(db.get(key).unwrap().unwrap().try_into().unwrap());
+
+    // No existing value: the operands are summed from 0.
+    for n in [1u64, 2, 3] {
+        db.merge(b"fresh", n.to_le_bytes()).unwrap();
+    }
+    assert_eq!(get_u64(b"fresh"), 6);
+
+    db.put(b"base", 10u64.to_le_bytes()).unwrap();
+    db.merge(b"base", 5u64.to_le_bytes()).unwrap();
+    db.flush().unwrap();
+    db.merge(b"base", 7u64.to_le_bytes()).unwrap();
+    assert_eq!(get_u64(b"base"), 22);
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    assert_eq!(get_u64(b"base"), 22);
+    assert_eq!(get_u64(b"fresh"), 6);
+
+    let signed = db.cf_handle("signed").unwrap();
+    for n in [5i64, -8, 1] {
+        db.merge_cf(&signed, b"counter", n.to_le_bytes()).unwrap();
+    }
+    let value = db.get_cf(&signed, b"counter").unwrap().unwrap();
+    assert_eq!(i64::from_le_bytes(value.try_into().unwrap()), -2);
+
+    // Operands that are not 8 bytes long fail the merge.
+    db.merge(b"bad", b"abc").unwrap();
+    assert!(db.get(b"bad").is_err());
+}
+
+#[test]
+fn concat_merge_operator_test() {
+    let path = DBPath::new("_rust_rocksdb_concat_merge_operator_test");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut plain_opts = Options::default();
+    plain_opts.set_merge_operator_associative("concat", merge_operator::concat(None));
+    opts.set_merge_operator_associative("concat_comma", merge_operator::concat(Some(b',')));
+
+    let db = DB::open_cf_descriptors(
+        &opts,
+        &path,
+        vec![rust_rocksdb::ColumnFamilyDescriptor::new(
+            "plain", plain_opts,
+        )],
+    )
+    .unwrap();
+
+    // No existing value: no leading separator.
+    for op in ["a", "b", "c"] {
+        db.merge(b"fresh", op).unwrap();
+    }
+    assert_eq!(db.get(b"fresh").unwrap().unwrap(), b"a,b,c");
+
+    db.put(b"base", b"x").unwrap();
+    db.merge(b"base", b"y").unwrap();
+    db.flush().unwrap();
+    db.merge(b"base", b"z").unwrap();
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    assert_eq!(db.get(b"base").unwrap().unwrap(), b"x,y,z");
+
+    let plain = db.cf_handle("plain").unwrap();
+    for op in ["a", "b", "c"] {
+        db.merge_cf(&plain, b"key", op).unwrap();
+    }
+    assert_eq!(db.get_cf(&plain, b"key").unwrap().unwrap(), b"abc");
+}
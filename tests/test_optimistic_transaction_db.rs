@@ -582,6 +582,34 @@ fn transaction_snapshot() {
     }
 }
 
+#[test]
+fn transaction_snapshot_validation() {
+    let path = DBPath::new("_rust_rocksdb_optimistic_transaction_db_snapshot_validation");
+    {
+        let db: OptimisticTransactionDB = OptimisticTransactionDB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        // without a snapshot, conflicts are checked from the time of the read,
+        // so a write that happened before it does not conflict
+        let txn = db.transaction();
+        db.put(b"k1", b"v2").unwrap();
+        assert_eq!(txn.get_for_update(b"k1", true).unwrap().unwrap(), b"v2");
+        txn.put(b"k1", b"v3").unwrap();
+        txn.commit().unwrap();
+
+        // with a snapshot, conflicts are checked from the start of the
+        // transaction, so the same sequence fails at commit
+        let mut opts = OptimisticTransactionOptions::default();
+        opts.set_snapshot(true);
+        let txn = db.transaction_opt(&WriteOptions::default(), &opts);
+        db.put(b"k1", b"v4").unwrap();
+        txn.get_for_update(b"k1", true).unwrap();
+        txn.put(b"k1", b"v5").unwrap();
+        assert_eq!(txn.commit().unwrap_err().kind(), ErrorKind::Busy);
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v4");
+    }
+}
+
 #[test]
 fn delete_range_test() {
     let path = DBPath::new("_rust_rocksdb_optimistic_transaction_db_delete_range_test");
@@ -16,7 +16,7 @@ mod util;
 
 use pretty_assertions::assert_eq;
 
-use rust_rocksdb::{properties, Options, DB};
+use rust_rocksdb::{properties, ColumnFamilyDescriptor, Options, DB, DEFAULT_COLUMN_FAMILY_NAME};
 use util::DBPath;
 
 #[test]
@@ -95,3 +95,170 @@ fn property_int_cf_test() {
         assert_eq!(total_keys, Some(0));
     }
 }
+
+#[test]
+fn property_constants_test() {
+    let n = DBPath::new("_rust_rocksdb_property_constants_test");
+    {
+        let db = DB::open_default(&n).unwrap();
+        for i in 0..100 {
+            db.put(format!("key{i}"), b"value").unwrap();
+        }
+        db.flush().unwrap();
+
+        let num_keys = db
+            .property_int_value(properties::ESTIMATE_NUM_KEYS)
+            .unwrap();
+        assert_eq!(num_keys, Some(100));
+        assert_eq!(
+            db.property_int_value(properties::num_files_at_level(0))
+                .unwrap(),
+            Some(1)
+        );
+
+        // every string property is known to RocksDB
+        for name in [
+            properties::STATS,
+            properties::SSTABLES,
+            properties::CFSTATS,
+            properties::CFSTATS_NO_FILE_HISTOGRAM,
+            properties::CF_FILE_HISTOGRAM,
+            properties::DBSTATS,
+            properties::LEVELSTATS,
+            properties::AGGREGATED_TABLE_PROPERTIES,
+        ] {
+            assert!(db.property_value(name).unwrap().is_some(), "{name:?}");
+        }
+        let cfstats = db.property_value(properties::CFSTATS).unwrap().unwrap();
+        assert!(cfstats.contains("Compaction Stats"));
+    }
+}
+
+#[test]
+fn cf_stats_test() {
+    let n = DBPath::new("_rust_rocksdb_cf_stats_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &n, ["cf1"]).unwrap();
+        let cf = db.cf_handle("cf1").unwrap();
+        for i in 0..100 {
+            db.put_cf(&cf, format!("key{i}"), b"value").unwrap();
+        }
+        db.flush_cf(&cf).unwrap();
+        db.put_cf(&cf, b"key100", b"value").unwrap();
+
+        let stats = db.cf_stats(&cf).unwrap();
+        assert!(stats.estimate_num_keys > 0);
+        assert!(stats.estimate_live_data_size > 0);
+        assert_eq!(stats.level0_file_count, 1);
+        assert_eq!(stats.num_immutable_mem_table, 0);
+        assert_eq!(stats.mem_table_flush_pending, 0);
+
+        let default = db.cf_handle(DEFAULT_COLUMN_FAMILY_NAME).unwrap();
+        let default_stats = db.cf_stats(&default).unwrap();
+        assert_eq!(default_stats.estimate_num_keys, 0);
+        assert_eq!(default_stats.level0_file_count, 0);
+    }
+}
+
+#[test]
+fn number_levels_test() {
+    let n = DBPath::new("_rust_rocksdb_number_levels_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cf_opts = Options::default();
+        cf_opts.set_num_levels(5);
+        let db =
+            DB::open_cf_descriptors(&opts, &n, vec![ColumnFamilyDescriptor::new("cf1", cf_opts)])
+                .unwrap();
+        let cf = db.cf_handle("cf1").unwrap();
+
+        assert_eq!(db.number_levels().unwrap(), 7);
+        assert_eq!(db.number_levels_cf(&cf).unwrap(), 5);
+        assert_eq!(db.max_level_with_files().unwrap(), None);
+        assert_eq!(db.max_level_with_files_cf(&cf).unwrap(), None);
+
+        db.put_cf(&cf, b"key", b"value").unwrap();
+        db.flush_cf(&cf).unwrap();
+        assert_eq!(db.max_level_with_files_cf(&cf).unwrap(), Some(0));
+
+        // dynamic level sizing moves the data straight to the last level
+        db.compact_range_cf(&cf, None::<&[u8]>, None::<&[u8]>);
+        assert_eq!(db.max_level_with_files_cf(&cf).unwrap(), Some(4));
+    }
+}
+
+#[test]
+#[cfg(feature = "zstd")]
+fn compression_ratio_at_level_test() {
+    let n = DBPath::new("_rust_rocksdb_compression_ratio_at_level_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let mut cf_opts = Options::default();
+        cf_opts.set_compression_type(rust_rocksdb::DBCompressionType::Zstd);
+        let db =
+            DB::open_cf_descriptors(&opts, &n, vec![ColumnFamilyDescriptor::new("cf1", cf_opts)])
+                .unwrap();
+        let cf = db.cf_handle("cf1").unwrap();
+        assert_eq!(db.compression_ratio_at_level_cf(&cf, 0).unwrap(), None);
+
+        let value = b"abcdefgh".repeat(128);
+        for i in 0..1000 {
+            db.put_cf(&cf, format!("key{i:04}"), &value).unwrap();
+        }
+        db.flush_cf(&cf).unwrap();
+        let ratio = db.compression_ratio_at_level_cf(&cf, 0).unwrap().unwrap();
+        assert!(ratio > 1.0, "unexpected compression ratio {ratio}");
+        assert_eq!(db.compression_ratio_at_level_cf(&cf, 1).unwrap(), None);
+
+        // the default column family holds no files
+        assert_eq!(db.compression_ratio_at_level(0).unwrap(), None);
+        // past the configured number of levels
+        assert_eq!(db.compression_ratio_at_level(100).unwrap(), None);
+    }
+}
+
+#[test]
+fn compaction_stats_test() {
+    let n = DBPath::new("_rust_rocksdb_compaction_stats_test");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_disable_auto_compactions(true);
+        let db = DB::open(&opts, &n).unwrap();
+
+        // interleaved keys make the L0 files overlap, so compacting them
+        // rewrites the data instead of trivially moving the files
+        for batch in 0..4 {
+            for i in 0..100 {
+                db.put(format!("key{:04}", i * 4 + batch), [b'x'; 100])
+                    .unwrap();
+            }
+            db.flush().unwrap();
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        let stats = db.compaction_stats().unwrap();
+        assert_eq!(stats.levels.keys().copied().collect::<Vec<_>>(), [0, 6]);
+        let l0 = &stats.levels[&0];
+        assert_eq!(l0.num_files, 0);
+        assert_eq!(l0.compaction_count, 4);
+        let l6 = &stats.levels[&6];
+        assert_eq!(l6.num_files, 1);
+        assert_eq!(l6.compaction_count, 1);
+        assert_eq!(l6.keys_in, 400);
+        assert_eq!(l6.keys_dropped, 0);
+        assert_eq!(stats.sum.num_files, 1);
+        assert!(
+            stats.sum.write_amplification > 1.0,
+            "unexpected write amplification {}",
+            stats.sum.write_amplification
+        );
+    }
+}
@@ -14,9 +14,11 @@
 
 mod util;
 
+use std::ops::ControlFlow;
+
 use pretty_assertions::assert_eq;
 
-use rust_rocksdb::{DBAccess, DBRawIteratorWithThreadMode, DB};
+use rust_rocksdb::{DBAccess, DBRawIteratorWithThreadMode, WriteBatch, DB};
 use util::DBPath;
 
 fn assert_item<D: DBAccess>(iter: &DBRawIteratorWithThreadMode<'_, D>, key: &[u8], value: &[u8]) {
@@ -150,3 +152,43 @@ pub fn test_next_without_seek() {
         iter.next();
     }
 }
+
+#[test]
+pub fn test_scan() {
+    let n = DBPath::new("_rust_rocksdb_raw_iterator_scan");
+    {
+        let db = DB::open_default(&n).unwrap();
+        let mut batch = WriteBatch::default();
+        for i in 0..100_000u32 {
+            batch.put(i.to_be_bytes(), [b'v'; 4]);
+        }
+        db.write(batch).unwrap();
+
+        let mut iter = db.raw_iterator();
+        iter.seek_to_first();
+        let mut count = 0u32;
+        let mut total_len = 0;
+        iter.scan(|key, value| {
+            assert_eq!(key, count.to_be_bytes());
+            count += 1;
+            total_len += value.len();
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(count, 100_000);
+        assert_eq!(total_len, 400_000);
+        assert!(!iter.valid());
+
+        // stops at the entry that breaks, leaving the iterator on it
+        iter.seek_to_first();
+        iter.scan(|key, _| {
+            if key == 10u32.to_be_bytes() {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+        assert_item(&iter, &10u32.to_be_bytes(), b"vvvv");
+    }
+}
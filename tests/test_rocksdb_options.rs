@@ -14,13 +14,16 @@
 
 mod util;
 
-use std::{fs, io::Read as _};
+use std::fs;
 
 use rust_rocksdb::{
-    checkpoint::Checkpoint, BlockBasedOptions, BlockBasedPinningTier, Cache, DBCompactionPri,
-    DBCompressionType, DataBlockIndexType, Env, LruCacheOptions, Options, ReadOptions, DB,
+    checkpoint::Checkpoint, properties, BlockBasedOptions, BlockBasedPinningTier,
+    BottommostLevelCompaction, Cache, CompactOptions, DBCompactionPri, DBCompressionType,
+    DataBlockIndexType, Env, ErrorKind, InplaceUpdateResult, LruCacheOptions, Options,
+    PrepopulateBlobCache, ReadOptions, SliceTransform, WaitForCompactOptions, DB,
+    DEFAULT_COLUMN_FAMILY_NAME,
 };
-use util::DBPath;
+use util::{read_log, DBPath};
 
 #[test]
 fn test_load_latest() {
@@ -97,10 +100,7 @@ fn test_block_based_options() {
         let _db = DB::open(&opts, &n).unwrap();
 
         // read the setting from the LOG file
-        let mut rocksdb_log = fs::File::open(format!("{}/LOG", (&n).as_ref().to_str().unwrap()))
-            .expect("rocksdb creates a LOG file");
-        let mut settings = String::new();
-        rocksdb_log.read_to_string(&mut settings).unwrap();
+        let settings = read_log(&n);
 
         // check the settings are set in the LOG file
         assert!(settings.contains("cache_index_and_filter_blocks: 1"));
@@ -146,12 +146,7 @@ fn test_set_data_block_index_type() {
         opts.set_block_based_table_factory(&block_opts);
         let _db = DB::open(&opts, &n).expect("open a db works");
 
-        let mut rocksdb_log = fs::File::open(format!("{}/LOG", (&n).as_ref().to_str().unwrap()))
-            .expect("rocksdb creates a LOG file");
-        let mut settings = String::new();
-        rocksdb_log
-            .read_to_string(&mut settings)
-            .expect("can read the LOG file");
+        let settings = read_log(&n);
         assert!(settings.contains("data_block_index_type: 0"));
         assert!(settings.contains("data_block_hash_table_util_ratio: 0.750000"));
     }
@@ -167,12 +162,7 @@ fn test_set_data_block_index_type() {
         opts.set_block_based_table_factory(&block_opts);
         let _db = DB::open(&opts, &n).expect("open a db works");
 
-        let mut rocksdb_log = fs::File::open(format!("{}/LOG", (&n).as_ref().to_str().unwrap()))
-            .expect("rocksdb creates a LOG file");
-        let mut settings = String::new();
-        rocksdb_log
-            .read_to_string(&mut settings)
-            .expect("can read the LOG file");
+        let settings = read_log(&n);
         assert!(settings.contains("data_block_index_type: 1"));
         assert!(settings.contains("data_block_hash_table_util_ratio: 0.350000"));
     }
@@ -277,15 +267,40 @@ fn test_add_compact_on_deletion_collector_factory() {
     opts.add_compact_on_deletion_collector_factory(5, 10, 0.5);
     let _db = DB::open(&opts, &n).unwrap();
 
-    let mut rocksdb_log = fs::File::open(format!("{}/LOG", (&n).as_ref().to_str().unwrap()))
-        .expect("rocksdb creates a LOG file");
-    let mut settings = String::new();
-    rocksdb_log
-        .read_to_string(&mut settings)
-        .expect("can read the LOG file");
+    let settings = read_log(&n);
     assert!(settings.contains("CompactOnDeletionCollector (Sliding window size = 5 Deletion trigger = 10 Deletion ratio = 0.5)"));
 }
 
+#[test]
+fn test_add_compact_on_deletion_collector_factory_simple() {
+    let n = DBPath::new("_rust_rocksdb_test_add_compact_on_deletion_collector_factory_simple");
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.add_compact_on_deletion_collector_factory_simple(10, 5);
+    let db = DB::open(&opts, &n).unwrap();
+
+    for i in 0..100 {
+        db.put(format!("key{i:03}"), "value").unwrap();
+    }
+    db.flush().unwrap();
+    for i in 0..100 {
+        db.delete(format!("key{i:03}")).unwrap();
+    }
+    db.flush().unwrap();
+
+    // two L0 files are below the L0 compaction trigger, so only the
+    // tombstone-heavy file being marked for compaction can move them out
+    db.wait_for_compact(&WaitForCompactOptions::default())
+        .unwrap();
+    assert_eq!(
+        db.property_int_value(properties::num_files_at_level(0))
+            .unwrap(),
+        Some(0)
+    );
+    assert!(db.get("key000").unwrap().is_none());
+}
+
 #[test]
 fn test_set_avoid_unnecessary_blocking_io() {
     let path = DBPath::new("_set_avoid_unnecessary_blocking_io");
@@ -327,8 +342,14 @@ fn test_set_periodic_compaction_seconds() {
         let mut opts = Options::default();
         opts.create_if_missing(true);
         opts.set_periodic_compaction_seconds(5);
-        let _db = DB::open(&opts, &path).unwrap();
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
     }
+
+    let settings = read_log(&path);
+    assert!(settings.contains("Options.periodic_compaction_seconds: 5"));
 }
 
 #[test]
@@ -338,8 +359,14 @@ fn test_set_ttl() {
         let mut opts = Options::default();
         opts.create_if_missing(true);
         opts.set_ttl(5);
-        let _db = DB::open(&opts, &path).unwrap();
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
     }
+
+    let settings = read_log(&path);
+    assert!(settings.contains("Options.ttl: 5"));
 }
 
 #[test]
@@ -426,6 +453,123 @@ fn test_set_blob_cache() {
     assert_eq!(&*db.get(KEY).unwrap().unwrap(), VALUE);
 }
 
+#[test]
+fn test_blob_cache_usage_grows() {
+    let path = DBPath::new("_blob_cache_usage_grows");
+    let cache = Cache::new_lru_cache(16 * 1024 * 1024);
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_enable_blob_files(true);
+    opts.set_min_blob_size(1024);
+    opts.set_blob_cache(&cache);
+
+    let db = DB::open(&opts, &path).unwrap();
+    let value = vec![b'v'; 64 * 1024];
+    for i in 0..16 {
+        db.put(format!("key{i:02}"), &value).unwrap();
+    }
+    // move the values out of the memtable into blob files
+    db.flush().unwrap();
+    assert_eq!(cache.get_usage(), 0);
+
+    for _ in 0..3 {
+        for i in 0..16 {
+            assert_eq!(db.get(format!("key{i:02}")).unwrap().unwrap(), value);
+        }
+    }
+    assert!(cache.get_usage() >= 16 * value.len());
+}
+
+#[test]
+fn test_set_blob_file_starting_level_and_prepopulate_blob_cache() {
+    let value = vec![b'v'; 4 * 1024];
+    let num_blob_files = |db: &DB| {
+        db.property_int_value("rocksdb.num-blob-files")
+            .unwrap()
+            .unwrap()
+    };
+
+    {
+        let path = DBPath::new("_set_blob_file_starting_level");
+        let cache = Cache::new_lru_cache(16 * 1024 * 1024);
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_enable_blob_files(true);
+        opts.set_min_blob_size(1024);
+        opts.set_blob_cache(&cache);
+        opts.set_prepopulate_blob_cache(PrepopulateBlobCache::FlushOnly);
+        opts.set_blob_file_starting_level(1);
+
+        let db = DB::open(&opts, &path).unwrap();
+        // two overlapping files, so that compaction can't just move them
+        for _ in 0..2 {
+            for i in 0..16 {
+                db.put(format!("key{i:02}"), &value).unwrap();
+            }
+            // flushed to L0, below the starting level, so values stay inline
+            db.flush().unwrap();
+        }
+        assert_eq!(num_blob_files(&db), 0);
+        assert_eq!(cache.get_usage(), 0);
+
+        // compaction moves the data past L0 and extracts the values
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert!(num_blob_files(&db) > 0);
+        for i in 0..16 {
+            assert_eq!(db.get(format!("key{i:02}")).unwrap().unwrap(), value);
+        }
+    }
+    {
+        let path = DBPath::new("_set_prepopulate_blob_cache");
+        let cache = Cache::new_lru_cache(16 * 1024 * 1024);
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_enable_blob_files(true);
+        opts.set_min_blob_size(1024);
+        opts.set_blob_cache(&cache);
+        opts.set_prepopulate_blob_cache(PrepopulateBlobCache::FlushOnly);
+
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..16 {
+            db.put(format!("key{i:02}"), &value).unwrap();
+        }
+        // blobs written by the flush are inserted without being read
+        db.flush().unwrap();
+        assert!(num_blob_files(&db) > 0);
+        assert!(cache.get_usage() >= 16 * value.len());
+        for i in 0..16 {
+            assert_eq!(db.get(format!("key{i:02}")).unwrap().unwrap(), value);
+        }
+    }
+}
+
+#[test]
+fn test_try_new_cache() {
+    let mut lru_opts = LruCacheOptions::default();
+    lru_opts.set_capacity(1024 * 1024);
+    let caches = [
+        Cache::try_new_lru_cache(1024 * 1024).unwrap(),
+        Cache::try_new_lru_cache_opts(&lru_opts).unwrap(),
+        Cache::try_new_hyper_clock_cache(1024 * 1024, 4 * 1024).unwrap(),
+    ];
+
+    for cache in &caches {
+        let path = DBPath::new("_try_new_cache");
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_block_cache(cache);
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_block_based_table_factory(&block_opts);
+
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert!(cache.get_usage() > 0);
+    }
+}
+
 #[test]
 fn test_lru_cache_custom_opts() {
     let path = DBPath::new("_set_blob_cache");
@@ -489,6 +633,7 @@ fn test_set_write_dbid_to_manifest() {
     opts.set_write_dbid_to_manifest(true);
     let db_orig = DB::open(&opts, &path).unwrap();
     let db_orig_id = db_orig.get_db_identity().unwrap();
+    assert!(!db_orig_id.is_empty());
 
     // a checkpoint from this database has the SAME DBID if it is in the manifest
     let checkpoint_path = DBPath::new("set_write_dbid_checkpoint");
@@ -505,3 +650,772 @@ fn test_set_write_dbid_to_manifest() {
         String::from_utf8_lossy(&db_checkpoint_id)
     );
 }
+
+#[test]
+fn test_set_optimize_filters_for_hits() {
+    fn bottommost_filter_size(optimize_filters_for_hits: bool) -> u64 {
+        let path = DBPath::new("_set_optimize_filters_for_hits");
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_bloom_filter(10.0, false);
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_block_based_table_factory(&block_opts);
+        opts.set_optimize_filters_for_hits(optimize_filters_for_hits);
+
+        let db = DB::open(&opts, &path).unwrap();
+        // two overlapping L0 files, so that compaction rewrites them instead
+        // of moving a file with its filter down
+        for _ in 0..2 {
+            for i in 0..1000 {
+                db.put(format!("key{i:04}"), b"value").unwrap();
+            }
+            db.flush().unwrap();
+        }
+        let mut compact_opts = CompactOptions::default();
+        compact_opts.set_bottommost_level_compaction(BottommostLevelCompaction::Force);
+        db.compact_range_opt(None::<&[u8]>, None::<&[u8]>, &compact_opts);
+        assert!(db.live_files().unwrap().iter().all(|f| f.level == 6));
+        for i in 0..1000 {
+            assert!(db.get(format!("key{i:04}")).unwrap().is_some());
+        }
+
+        let props = db
+            .property_value(properties::AGGREGATED_TABLE_PROPERTIES)
+            .unwrap()
+            .unwrap();
+        props
+            .split(';')
+            .find_map(|prop| prop.trim().strip_prefix("filter block size="))
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    assert!(bottommost_filter_size(false) > 0);
+    assert_eq!(bottommost_filter_size(true), 0);
+}
+
+#[test]
+fn test_set_memtable_max_range_deletions() {
+    let path = DBPath::new("_set_memtable_max_range_deletions");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_memtable_max_range_deletions(2);
+        let db = DB::open_cf(&opts, &path, [DEFAULT_COLUMN_FAMILY_NAME]).unwrap();
+        let cf = db.cf_handle(DEFAULT_COLUMN_FAMILY_NAME).unwrap();
+        db.put(b"k0", b"v0").unwrap();
+        for i in 0..3 {
+            db.delete_range_cf(&cf, format!("a{i}"), format!("b{i}"))
+                .unwrap();
+        }
+        // the flush is scheduled by the write following the one that crossed the limit
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut flushed = false;
+        for _ in 0..100 {
+            if !db.live_files().unwrap().is_empty() {
+                flushed = true;
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        assert!(flushed, "range deletions did not trigger a flush");
+        assert_eq!(db.get(b"k0").unwrap().unwrap(), b"v0");
+    }
+}
+
+#[test]
+fn test_set_experimental_mempurge_threshold() {
+    let path = DBPath::new("_set_experimental_mempurge_threshold");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_experimental_mempurge_threshold(1.0);
+        opts.set_write_buffer_size(64 * 1024);
+        let db = DB::open(&opts, &path).unwrap();
+        for round in 0..1000 {
+            for key in 0..10 {
+                db.put(format!("key{key}"), format!("value{round}"))
+                    .unwrap();
+            }
+        }
+        for key in 0..10 {
+            assert_eq!(db.get(format!("key{key}")).unwrap().unwrap(), b"value999");
+        }
+    }
+}
+
+#[test]
+fn test_set_direct_io_with_writable_file_max_buffer_size() {
+    let path = DBPath::new("_set_direct_io_with_writable_file_max_buffer_size");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_use_direct_reads(true);
+        opts.set_use_direct_io_for_flush_and_compaction(true);
+        opts.set_writable_file_max_buffer_size(1024 * 1024);
+        let db = match DB::open(&opts, &path) {
+            Ok(db) => db,
+            // the filesystem backing the temporary directory may not support O_DIRECT
+            Err(_) => return,
+        };
+        for i in 0..1000 {
+            db.put(format!("key{i:04}"), format!("value{i:04}"))
+                .unwrap();
+        }
+        db.flush().unwrap();
+        for i in 0..1000 {
+            assert_eq!(
+                db.get(format!("key{i:04}")).unwrap().unwrap(),
+                format!("value{i:04}").as_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_set_stats_dump_and_persist_period_sec() {
+    let path = DBPath::new("_set_stats_dump_and_persist_period_sec");
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.enable_statistics();
+        opts.set_stats_dump_period_sec(1);
+        opts.set_stats_persist_period_sec(1);
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"a").unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"a");
+
+        // the periodic dump shows up in the info LOG within a few periods
+        let mut dumped = false;
+        for _ in 0..100 {
+            if read_log(&path).contains("DUMPING STATS") {
+                dumped = true;
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        assert!(dumped, "stats were not dumped to LOG");
+    }
+}
+
+#[test]
+fn test_set_max_file_opening_threads() {
+    let path = DBPath::new("_set_max_file_opening_threads");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_max_open_files(-1);
+    opts.set_max_file_opening_threads(8);
+    opts.set_table_cache_num_shard_bits(4);
+    opts.set_disable_auto_compactions(true);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for file in 0..32 {
+            db.put(format!("key{file:02}"), format!("value{file:02}"))
+                .unwrap();
+            db.flush().unwrap();
+        }
+        assert_eq!(db.live_files().unwrap().len(), 32);
+    }
+    {
+        // with max_open_files = -1 all table files are opened during DB::open
+        let db = DB::open(&opts, &path).unwrap();
+        for file in 0..32 {
+            assert_eq!(
+                db.get(format!("key{file:02}")).unwrap().unwrap(),
+                format!("value{file:02}").as_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_set_max_background_compactions_and_flushes() {
+    let path = DBPath::new("_set_max_background_compactions_and_flushes");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    assert_eq!(opts.get_max_background_compactions(), -1);
+    assert_eq!(opts.get_max_background_flushes(), -1);
+
+    opts.set_max_background_jobs(6);
+    opts.set_max_background_compactions(4);
+    opts.set_max_background_flushes(2);
+    assert_eq!(opts.get_max_background_jobs(), 6);
+    assert_eq!(opts.get_max_background_compactions(), 4);
+    assert_eq!(opts.get_max_background_flushes(), 2);
+
+    let db = DB::open(&opts, &path).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+    assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+}
+
+#[test]
+fn test_set_allow_mmap_reads() {
+    let path = DBPath::new("_set_allow_mmap_reads");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_allow_mmap_reads(true);
+    opts.set_allow_mmap_writes(true);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..100 {
+            db.put(format!("key{i:03}"), format!("value{i:03}"))
+                .unwrap();
+        }
+        db.flush().unwrap();
+    }
+    {
+        // reads are now served from the memory mapped table files
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..100 {
+            assert_eq!(
+                db.get(format!("key{i:03}")).unwrap().unwrap(),
+                format!("value{i:03}").as_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_mmap_and_direct_io_conflict() {
+    let path = DBPath::new("_mmap_and_direct_io_conflict");
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_allow_mmap_reads(true);
+    opts.set_use_direct_reads(true);
+    let err = DB::open(&opts, &path).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotSupported);
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_allow_mmap_writes(true);
+    opts.set_use_direct_io_for_flush_and_compaction(true);
+    let err = DB::open(&opts, &path).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotSupported);
+}
+
+#[test]
+fn test_set_protection_bytes_per_key() {
+    let path = DBPath::new("_set_protection_bytes_per_key");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_memtable_protection_bytes_per_key(8);
+    opts.set_block_protection_bytes_per_key(8);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..100 {
+            db.put(format!("key{i:03}"), format!("value{i:03}"))
+                .unwrap();
+        }
+        // served from the protected memtable
+        assert_eq!(db.get(b"key042").unwrap().unwrap(), b"value042");
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        // served from protected blocks
+        for i in 0..100 {
+            assert_eq!(
+                db.get(format!("key{i:03}")).unwrap().unwrap(),
+                format!("value{i:03}").as_bytes()
+            );
+        }
+    }
+
+    let settings = read_log(&path);
+    assert!(settings.contains("Options.memtable_protection_bytes_per_key: 8"));
+    assert!(settings.contains("Options.block_protection_bytes_per_key: 8"));
+}
+
+#[test]
+fn test_set_protection_bytes_per_key_unsupported() {
+    let path = DBPath::new("_set_protection_bytes_per_key_unsupported");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_memtable_protection_bytes_per_key(3);
+    assert!(DB::open(&opts, &path).is_err());
+}
+
+#[test]
+fn test_set_paranoid_and_force_consistency_checks() {
+    let path = DBPath::new("_set_paranoid_and_force_consistency_checks");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_paranoid_checks(true);
+    opts.set_force_consistency_checks(true);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for round in 0..4 {
+            for i in 0..100 {
+                db.put(format!("key{i:03}"), format!("value{round}"))
+                    .unwrap();
+            }
+            db.flush().unwrap();
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        db.wait_for_compact(&WaitForCompactOptions::default())
+            .unwrap();
+        assert_eq!(
+            db.property_int_value(properties::BACKGROUND_ERRORS)
+                .unwrap(),
+            Some(0)
+        );
+        assert_eq!(db.get(b"key000").unwrap().unwrap(), b"value3");
+    }
+
+    let settings = read_log(&path);
+    assert!(settings.contains("Options.force_consistency_checks: 1"));
+}
+
+#[test]
+fn test_optimize_for_point_lookup() {
+    let path = DBPath::new("_optimize_for_point_lookup");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.optimize_for_point_lookup(64);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..1000 {
+            db.put(format!("key{i:04}"), format!("value{i:04}"))
+                .unwrap();
+        }
+        db.flush().unwrap();
+        for i in 0..1000 {
+            assert_eq!(
+                db.get(format!("key{i:04}")).unwrap().unwrap(),
+                format!("value{i:04}").as_bytes()
+            );
+        }
+        assert!(db.get(b"missing").unwrap().is_none());
+    }
+}
+
+#[test]
+fn test_optimize_compaction_styles() {
+    for universal in [false, true] {
+        let path = DBPath::new("_optimize_compaction_styles");
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        if universal {
+            opts.optimize_universal_style_compaction(64 * 1024 * 1024);
+        } else {
+            opts.optimize_level_style_compaction(64 * 1024 * 1024);
+        }
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+fn test_block_cache_outlives_block_based_options() {
+    let path = DBPath::new("_block_cache_outlives_block_based_options");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    {
+        let cache = Cache::new_lru_cache(1024 * 1024);
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_block_cache(&cache);
+        opts.set_block_based_table_factory(&block_opts);
+        // `Options` keeps its own reference to the cache, so dropping both
+        // the table options and the caller's handle must be safe.
+        drop(block_opts);
+        drop(cache);
+    }
+
+    let db = DB::open(&opts, &path).unwrap();
+    for i in 0..100 {
+        db.put(format!("key{i:03}"), format!("value{i:03}"))
+            .unwrap();
+    }
+    db.flush().unwrap();
+    for _ in 0..3 {
+        for i in 0..100 {
+            assert_eq!(
+                db.get(format!("key{i:03}")).unwrap().unwrap(),
+                format!("value{i:03}").as_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_set_inplace_update_support() {
+    let path = DBPath::new("_set_inplace_update_support");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_inplace_update_support(true);
+    opts.set_inplace_update_locks(16);
+    let db = DB::open(&opts, &path).unwrap();
+
+    for i in 0u64..100 {
+        db.put(b"counter", i.to_le_bytes()).unwrap();
+    }
+    let value = db.get(b"counter").unwrap().unwrap();
+    assert_eq!(u64::from_le_bytes(value.try_into().unwrap()), 99);
+}
+
+#[test]
+fn test_set_inplace_update_callback() {
+    fn add_counter(existing: Option<&mut [u8]>, delta: &[u8]) -> InplaceUpdateResult {
+        let Ok(delta) = <[u8; 8]>::try_from(delta) else {
+            return InplaceUpdateResult::Failed;
+        };
+        match existing {
+            Some(existing) => {
+                let sum = u64::from_le_bytes(existing[..8].try_into().unwrap())
+                    + u64::from_le_bytes(delta);
+                existing[..8].copy_from_slice(&sum.to_le_bytes());
+                InplaceUpdateResult::UpdatedInplace(8)
+            }
+            None => InplaceUpdateResult::Updated(delta.to_vec()),
+        }
+    }
+
+    let path = DBPath::new("_set_inplace_update_callback");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_inplace_update_support(true);
+    opts.set_inplace_update_callback(add_counter);
+    let db = DB::open(&opts, &path).unwrap();
+
+    for _ in 0..100 {
+        db.put(b"counter", 1u64.to_le_bytes()).unwrap();
+    }
+    // rejected by the callback
+    db.put(b"counter", b"one").unwrap();
+
+    let value = db.get(b"counter").unwrap().unwrap();
+    assert_eq!(u64::from_le_bytes(value.try_into().unwrap()), 100);
+    // all updates after the first one went to the same memtable entry
+    assert_eq!(
+        db.property_int_value(properties::NUM_ENTRIES_ACTIVE_MEM_TABLE)
+            .unwrap(),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_set_background_error_recovery() {
+    let path = DBPath::new("_set_background_error_recovery");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_best_efforts_recovery(true);
+    opts.set_max_bgerror_resume_count(5);
+    opts.set_bgerror_resume_retry_interval(200_000);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+    }
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(
+            db.property_int_value(properties::BACKGROUND_ERRORS)
+                .unwrap(),
+            Some(0)
+        );
+    }
+
+    let settings = read_log(&path);
+    assert!(settings.contains("Options.best_efforts_recovery: 1"));
+    assert!(settings.contains("Options.max_bgerror_resume_count: 5"));
+    assert!(settings.contains("Options.bgerror_resume_retry_interval: 200000"));
+}
+
+#[test]
+fn test_skip_work_on_db_open() {
+    let path = DBPath::new("_skip_work_on_db_open");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_disable_auto_compactions(true);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for file in 0..20 {
+            for i in 0..50 {
+                db.put(format!("key{file:02}{i:02}"), format!("value{file}"))
+                    .unwrap();
+            }
+            db.flush().unwrap();
+        }
+    }
+
+    opts.set_skip_stats_update_on_db_open(true);
+    opts.set_skip_checking_sst_file_sizes_on_db_open(true);
+    let db = DB::open(&opts, &path).unwrap();
+    assert_eq!(db.live_files().unwrap().len(), 20);
+    for file in 0..20 {
+        for i in 0..50 {
+            assert_eq!(
+                db.get(format!("key{file:02}{i:02}")).unwrap().unwrap(),
+                format!("value{file}").as_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_set_max_write_batch_group_size_bytes() {
+    let path = DBPath::new("_set_max_write_batch_group_size_bytes");
+    let mut opts = Options::default();
+    assert_eq!(opts.get_max_write_batch_group_size_bytes(), 1 << 20);
+    opts.set_max_write_batch_group_size_bytes(4 * 1024);
+    assert_eq!(opts.get_max_write_batch_group_size_bytes(), 4 * 1024);
+    opts.create_if_missing(true);
+
+    let db = DB::open(&opts, &path).unwrap();
+    std::thread::scope(|s| {
+        for t in 0..8 {
+            let db = &db;
+            s.spawn(move || {
+                for i in 0..500 {
+                    db.put(format!("t{t}-key{i:03}"), [b'x'; 100]).unwrap();
+                }
+            });
+        }
+    });
+    for t in 0..8 {
+        for i in 0..500 {
+            assert_eq!(
+                db.get(format!("t{t}-key{i:03}")).unwrap().unwrap(),
+                [b'x'; 100]
+            );
+        }
+    }
+}
+
+#[test]
+fn test_log_file_recycling_and_retention() {
+    let path = DBPath::new("_rust_rocksdb_log_file_recycling_and_retention");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_recycle_log_file_num(2);
+    opts.set_keep_log_file_num(2);
+
+    for round in 0..5 {
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..100 {
+            db.put(format!("key{round}-{i:03}"), format!("value{i}"))
+                .unwrap();
+            if i % 10 == 9 {
+                db.flush().unwrap();
+            }
+        }
+    }
+
+    let db = DB::open(&opts, &path).unwrap();
+    for round in 0..5 {
+        for i in 0..100 {
+            assert_eq!(
+                db.get(format!("key{round}-{i:03}")).unwrap().unwrap(),
+                format!("value{i}").as_bytes()
+            );
+        }
+    }
+
+    let settings = read_log(&path);
+    assert!(settings.contains("Options.recycle_log_file_num: 2"));
+
+    // Every open rolls the info log; only the current LOG and the most recent
+    // old ones are kept.
+    let info_logs = fs::read_dir(&path)
+        .unwrap()
+        .filter(|entry| {
+            entry
+                .as_ref()
+                .unwrap()
+                .file_name()
+                .to_string_lossy()
+                .starts_with("LOG")
+        })
+        .count();
+    assert!(info_logs <= 3, "{info_logs} info log files kept");
+}
+
+#[test]
+fn test_set_write_thread_yield_options() {
+    let path = DBPath::new("_rust_rocksdb_set_write_thread_yield_options");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_enable_write_thread_adaptive_yield(true);
+    opts.set_write_thread_max_yield_usec(200);
+    opts.set_write_thread_slow_yield_usec(5);
+
+    let db = DB::open(&opts, &path).unwrap();
+    std::thread::scope(|s| {
+        for t in 0..8 {
+            let db = &db;
+            s.spawn(move || {
+                for i in 0..500 {
+                    db.put(format!("t{t}-key{i:03}"), format!("value{i}"))
+                        .unwrap();
+                }
+            });
+        }
+    });
+    for t in 0..8 {
+        for i in 0..500 {
+            assert_eq!(
+                db.get(format!("t{t}-key{i:03}")).unwrap().unwrap(),
+                format!("value{i}").as_bytes()
+            );
+        }
+    }
+
+    let settings = read_log(&path);
+    assert!(settings.contains("Options.enable_write_thread_adaptive_yield: 1"));
+    assert!(settings.contains("Options.write_thread_max_yield_usec: 200"));
+    assert!(settings.contains("Options.write_thread_slow_yield_usec: 5"));
+}
+
+#[test]
+fn test_set_allow_data_in_errors() {
+    use std::io::{Seek as _, SeekFrom, Write as _};
+
+    let path = DBPath::new("_rust_rocksdb_set_allow_data_in_errors");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_compression_type(DBCompressionType::None);
+    opts.set_allow_data_in_errors(false);
+
+    let key = b"secret-key-0001";
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(key, b"secret-value").unwrap();
+        db.flush().unwrap();
+        let sst = db.live_files().unwrap().remove(0).name;
+
+        // Corrupt the start of the data block, which holds the key.
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .open((&path).as_ref().join(sst.trim_start_matches('/')))
+            .unwrap();
+        file.seek(SeekFrom::Start(5)).unwrap();
+        file.write_all(b"XXXX").unwrap();
+    }
+
+    let db = DB::open(&opts, &path).unwrap();
+    let err = db.get(key).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Corruption);
+    let message = err.into_string();
+    assert!(!message.contains("secret"), "{message}");
+
+    let settings = read_log(&path);
+    assert!(settings.contains("Options.allow_data_in_errors: 0"));
+    assert!(!settings.contains("secret"));
+}
+
+#[test]
+fn test_set_memtable_insert_with_hint_prefix_extractor() {
+    let path = DBPath::new("_rust_rocksdb_set_memtable_insert_with_hint_prefix_extractor");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    let prefix_extractor = SliceTransform::create_fixed_prefix(4);
+    opts.set_memtable_insert_with_hint_prefix_extractor(&prefix_extractor)
+        .unwrap();
+    opts.set_prefix_extractor(prefix_extractor);
+    // a custom transform is rejected; handing it to the prefix extractor
+    // afterwards lets the options free it
+    let mut custom_opts = Options::default();
+    let custom = SliceTransform::create("custom", |key| &key[..1], None);
+    assert!(custom_opts
+        .set_memtable_insert_with_hint_prefix_extractor(&custom)
+        .is_err());
+    custom_opts.set_prefix_extractor(custom);
+
+    let db = DB::open(&opts, &path).unwrap();
+    for prefix in ["aaaa", "bbbb", "cccc"] {
+        for ts in 0..1000u32 {
+            db.put(format!("{prefix}{ts:08}"), ts.to_le_bytes())
+                .unwrap();
+        }
+    }
+    for prefix in ["aaaa", "bbbb", "cccc"] {
+        for ts in 0..1000u32 {
+            assert_eq!(
+                db.get(format!("{prefix}{ts:08}")).unwrap().unwrap(),
+                ts.to_le_bytes()
+            );
+        }
+    }
+    assert_eq!(db.iterator(rust_rocksdb::IteratorMode::Start).count(), 3000);
+
+    let settings = read_log(&path);
+    assert!(settings
+        .contains("Options.memtable_insert_with_hint_prefix_extractor: rocksdb.FixedPrefix.4"));
+}
+
+#[test]
+fn test_effective_periodic_compaction_seconds() {
+    const THIRTY_DAYS: u64 = 30 * 24 * 60 * 60;
+
+    let mut opts = Options::default();
+    assert_eq!(opts.get_ttl(), u64::MAX - 1);
+    assert_eq!(opts.get_periodic_compaction_seconds(), u64::MAX - 1);
+    assert_eq!(
+        opts.effective_periodic_compaction_seconds(),
+        Some(THIRTY_DAYS)
+    );
+
+    opts.set_ttl(0);
+    opts.set_periodic_compaction_seconds(0);
+    assert_eq!(opts.effective_periodic_compaction_seconds(), None);
+
+    opts.set_ttl(3600);
+    assert_eq!(opts.effective_periodic_compaction_seconds(), Some(3600));
+    opts.set_periodic_compaction_seconds(600);
+    assert_eq!(opts.effective_periodic_compaction_seconds(), Some(600));
+
+    opts.set_ttl(0);
+    assert_eq!(opts.effective_periodic_compaction_seconds(), Some(600));
+
+    // A value left at its default counts as 30 days.
+    let mut opts = Options::default();
+    opts.set_periodic_compaction_seconds(2 * THIRTY_DAYS);
+    assert_eq!(
+        opts.effective_periodic_compaction_seconds(),
+        Some(THIRTY_DAYS)
+    );
+    opts.set_ttl(0);
+    assert_eq!(
+        opts.effective_periodic_compaction_seconds(),
+        Some(2 * THIRTY_DAYS)
+    );
+}
+
+#[test]
+fn test_set_two_write_queues() {
+    let path = DBPath::new("_rust_rocksdb_set_two_write_queues");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_two_write_queues(true);
+
+    let db = DB::open(&opts, &path).unwrap();
+    std::thread::scope(|s| {
+        for t in 0..8 {
+            let db = &db;
+            s.spawn(move || {
+                for i in 0..500 {
+                    db.put(format!("t{t}-key{i:03}"), format!("value{i}"))
+                        .unwrap();
+                }
+            });
+        }
+    });
+    for t in 0..8 {
+        for i in 0..500 {
+            assert_eq!(
+                db.get(format!("t{t}-key{i:03}")).unwrap().unwrap(),
+                format!("value{i}").as_bytes()
+            );
+        }
+    }
+    assert_eq!(db.latest_sequence_number(), 8 * 500);
+
+    let settings = read_log(&path);
+    assert!(settings.contains("Options.two_write_queues: 1"));
+}
@@ -78,3 +78,36 @@ fn test_no_in_domain() {
         assert_eq!(db.get(b"key_sfx1").unwrap().unwrap(), b"a");
     }
 }
+
+#[test]
+fn test_transform_and_in_domain() {
+    let fixed = SliceTransform::create_fixed_prefix(4);
+    assert_eq!(fixed.transform(b"abcdefgh"), Some(&b"abcd"[..]));
+    assert!(fixed.in_domain(b"abcd"));
+    assert!(!fixed.in_domain(b"abc"));
+
+    let noop = SliceTransform::create_noop();
+    assert_eq!(noop.transform(b"abcdefgh"), Some(&b"abcdefgh"[..]));
+    assert!(noop.in_domain(b""));
+
+    fn before_dot(key: &[u8]) -> &[u8] {
+        let end = key.iter().position(|&b| b == b'.').unwrap();
+        &key[..end]
+    }
+    fn has_dot(key: &[u8]) -> bool {
+        key.contains(&b'.')
+    }
+    let custom = SliceTransform::create("before_dot", before_dot, Some(has_dot));
+    assert_eq!(custom.transform(b"user.42"), None);
+    assert!(custom.in_domain(b"user.42"));
+    assert!(!custom.in_domain(b"user"));
+
+    let callable = SliceTransform::create_with_prefix_fn("before_dot", before_dot, Some(has_dot));
+    assert_eq!(callable.transform(b"user.42"), Some(&b"user"[..]));
+    assert!(!callable.in_domain(b"user"));
+
+    // only options free the transforms given to them
+    for transform in [fixed, noop, custom, callable] {
+        Options::default().set_prefix_extractor(transform);
+    }
+}
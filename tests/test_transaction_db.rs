@@ -19,7 +19,7 @@ use pretty_assertions::assert_eq;
 
 use rust_rocksdb::{
     CuckooTableOptions, DBAccess, Direction, Error, ErrorKind, IteratorMode, Options, ReadOptions,
-    SliceTransform, TransactionDB, TransactionDBOptions, TransactionOptions,
+    ReadTier, SliceTransform, TransactionDB, TransactionDBOptions, TransactionOptions,
     WriteBatchWithTransaction, WriteOptions, DB,
 };
 use util::DBPath;
@@ -163,6 +163,33 @@ fn multi_get() {
     }
 }
 
+#[test]
+fn multi_get_per_key_errors() {
+    let path = DBPath::new("_rust_rocksdb_multi_get_per_key_errors");
+
+    {
+        let db: TransactionDB = TransactionDB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+    }
+    {
+        // recovery flushes k1 to an SST whose data block isn't cached yet
+        let db: TransactionDB = TransactionDB::open_default(&path).unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_read_tier(ReadTier::BlockCache);
+        let mut values = db
+            .multi_get_opt([b"k1", b"k2", b"k9"], &readopts)
+            .into_iter();
+
+        let err = values.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Incomplete);
+        assert_eq!(values.next().unwrap().unwrap(), Some(b"v2".to_vec()));
+        assert_eq!(values.next().unwrap().unwrap(), None);
+        assert!(values.next().is_none());
+    }
+}
+
 #[test]
 fn multi_get_cf() {
     let path = DBPath::new("_rust_rocksdb_multi_get_cf");
@@ -506,6 +533,83 @@ fn transaction_iterator() {
     }
 }
 
+#[test]
+fn transaction_iterator_overlays_staged_writes() {
+    let path = DBPath::new("_rust_rocksdb_transaction_db_iterator_overlays_staged_writes");
+    {
+        let db: TransactionDB = TransactionDB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        let txn = db.transaction();
+        txn.delete(b"k1").unwrap();
+        txn.put(b"k3", b"v3").unwrap();
+
+        let expected: Vec<(Box<[u8]>, Box<[u8]>)> = vec![
+            (
+                b"k2".to_vec().into_boxed_slice(),
+                b"v2".to_vec().into_boxed_slice(),
+            ),
+            (
+                b"k3".to_vec().into_boxed_slice(),
+                b"v3".to_vec().into_boxed_slice(),
+            ),
+        ];
+        let iter = txn.iterator(IteratorMode::Start);
+        assert_eq!(iter.map(Result::unwrap).collect::<Vec<_>>(), expected);
+
+        let mut raw = txn.raw_iterator();
+        raw.seek_to_first();
+        assert_eq!(raw.key(), Some(&b"k2"[..]));
+
+        // the database itself is untouched until commit
+        assert!(db.get(b"k1").unwrap().is_some());
+        assert!(db.get(b"k3").unwrap().is_none());
+    }
+}
+
+#[test]
+fn transaction_writebatch_roundtrip() {
+    use rust_rocksdb::WriteBatchIterator;
+
+    struct Collector(Vec<(Box<[u8]>, Box<[u8]>)>);
+    impl WriteBatchIterator for Collector {
+        fn put(&mut self, key: Box<[u8]>, value: Box<[u8]>) {
+            self.0.push((key, value));
+        }
+        fn delete(&mut self, _key: Box<[u8]>) {
+            unreachable!();
+        }
+    }
+
+    let path = DBPath::new("_rust_rocksdb_transaction_db_writebatch_roundtrip");
+    {
+        let db: TransactionDB = TransactionDB::open_default(&path).unwrap();
+
+        let txn1 = db.transaction();
+        txn1.put(b"k1", b"v1").unwrap();
+        txn1.put(b"k2", b"v2").unwrap();
+        let wb = txn1.get_writebatch();
+        assert_eq!(wb.len(), 2);
+        txn1.rollback().unwrap();
+
+        let mut collector = Collector(Vec::new());
+        wb.iterate(&mut collector);
+        assert_eq!(collector.0.len(), 2);
+        assert_eq!(&*collector.0[0].0, b"k1");
+        assert_eq!(&*collector.0[0].1, b"v1");
+        assert_eq!(&*collector.0[1].0, b"k2");
+        assert_eq!(&*collector.0[1].1, b"v2");
+
+        let txn2 = db.transaction();
+        txn2.rebuild_from_writebatch(&wb).unwrap();
+        txn2.commit().unwrap();
+
+        assert_eq!(db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(db.get(b"k2").unwrap().unwrap(), b"v2");
+    }
+}
+
 #[test]
 fn transaction_rollback() {
     let path = DBPath::new("_rust_rocksdb_transaction_db_transaction_rollback");
@@ -600,6 +704,46 @@ fn transaction_snapshot() {
     }
 }
 
+#[test]
+fn transaction_deadlock_detect() {
+    let path = DBPath::new("_rust_rocksdb_transaction_db_deadlock_detect");
+    {
+        let db: TransactionDB = TransactionDB::open_default(&path).unwrap();
+
+        let mut opts = TransactionOptions::default();
+        opts.set_deadlock_detect(true);
+        // long enough that only deadlock detection can end the wait
+        opts.set_lock_timeout(10_000);
+        let txn1 = db.transaction_opt(&WriteOptions::default(), &opts);
+        let txn2 = db.transaction_opt(&WriteOptions::default(), &opts);
+        txn1.get_for_update(b"k1", true).unwrap();
+        txn2.get_for_update(b"k2", true).unwrap();
+
+        let (result1, result2) = std::thread::scope(|scope| {
+            let handle = scope.spawn(move || {
+                let result = txn1.get_for_update(b"k2", true);
+                if result.is_err() {
+                    txn1.rollback().unwrap();
+                }
+                result
+            });
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            let result = txn2.get_for_update(b"k1", true);
+            if result.is_err() {
+                txn2.rollback().unwrap();
+            }
+            (handle.join().unwrap(), result)
+        });
+
+        // exactly one side of the cycle is aborted, the other gets its lock
+        let err = match (result1, result2) {
+            (Err(err), Ok(_)) | (Ok(_), Err(err)) => err,
+            other => panic!("expected exactly one deadlock error, got {other:?}"),
+        };
+        assert_eq!(err.kind(), ErrorKind::Busy);
+    }
+}
+
 #[test]
 fn two_phase_commit() {
     let path = DBPath::new("_rust_rocksdb_transaction_db_2pc");
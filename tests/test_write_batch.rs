@@ -12,11 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod util;
+
 use std::collections::HashMap;
 
 use pretty_assertions::assert_eq;
 
-use rust_rocksdb::{WriteBatch, WriteBatchIterator};
+use rust_rocksdb::{
+    ErrorKind, Options, TransactionDB, TransactionDBOptions, WriteBatch, WriteBatchIterator,
+    WriteBatchIteratorCf, WriteBatchWithTransaction,
+};
+use util::DBPath;
 
 #[test]
 fn test_write_batch_clear() {
@@ -66,3 +72,106 @@ fn test_write_batch_with_serialized_data() {
     let mut it = Iterator { data: kvs };
     b2.iterate(&mut it);
 }
+
+#[test]
+fn test_write_batch_iterate_cf() {
+    #[derive(Default)]
+    struct Recorder {
+        ops: Vec<(&'static str, u32, Vec<u8>, Vec<u8>)>,
+    }
+
+    impl WriteBatchIteratorCf for Recorder {
+        fn put_cf(&mut self, cf_id: u32, key: &[u8], value: &[u8]) {
+            self.ops.push(("put", cf_id, key.to_vec(), value.to_vec()));
+        }
+
+        fn delete_cf(&mut self, cf_id: u32, key: &[u8]) {
+            self.ops.push(("delete", cf_id, key.to_vec(), Vec::new()));
+        }
+
+        fn single_delete_cf(&mut self, cf_id: u32, key: &[u8]) {
+            self.ops
+                .push(("single_delete", cf_id, key.to_vec(), Vec::new()));
+        }
+
+        fn delete_range_cf(&mut self, cf_id: u32, from: &[u8], to: &[u8]) {
+            self.ops
+                .push(("delete_range", cf_id, from.to_vec(), to.to_vec()));
+        }
+
+        fn merge_cf(&mut self, cf_id: u32, key: &[u8], value: &[u8]) {
+            self.ops
+                .push(("merge", cf_id, key.to_vec(), value.to_vec()));
+        }
+    }
+
+    let path = DBPath::new("_rust_rocksdb_write_batch_iterate_cf");
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db: TransactionDB =
+        TransactionDB::open_cf(&opts, &TransactionDBOptions::default(), &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    let mut batch = WriteBatchWithTransaction::<true>::default();
+    batch.put(b"k1", b"v1");
+    batch.put_cf(&cf1, b"k2", b"v2");
+    batch.merge_cf(&cf1, b"k3", b"v3");
+    batch.delete(b"k4");
+    batch.delete_cf(&cf1, b"k5");
+
+    let mut recorder = Recorder::default();
+    batch.iterate_cf(&mut recorder).unwrap();
+    assert_eq!(
+        recorder.ops,
+        [
+            ("put", 0, b"k1".to_vec(), b"v1".to_vec()),
+            ("put", 1, b"k2".to_vec(), b"v2".to_vec()),
+            ("merge", 1, b"k3".to_vec(), b"v3".to_vec()),
+            ("delete", 0, b"k4".to_vec(), Vec::new()),
+            ("delete", 1, b"k5".to_vec(), Vec::new()),
+        ]
+    );
+
+    // Iterating leaves the batch intact.
+    assert_eq!(batch.len(), 5);
+
+    let mut batch = WriteBatch::default();
+    batch.delete_range(b"k1", b"k2");
+    batch.delete_range_cf(&cf1, b"k3", b"k4");
+
+    let mut recorder = Recorder::default();
+    batch.iterate_cf(&mut recorder).unwrap();
+    assert_eq!(
+        recorder.ops,
+        [
+            ("delete_range", 0, b"k1".to_vec(), b"k2".to_vec()),
+            ("delete_range", 1, b"k3".to_vec(), b"k4".to_vec()),
+        ]
+    );
+
+    // There is no way to add single deletions, so build the serialized
+    // batch: sequence number, count, then a single deletion of "k1" and one
+    // of "k2" in column family 1.
+    let mut data = vec![0; 8];
+    data.extend_from_slice(&2u32.to_le_bytes());
+    data.extend_from_slice(&[0x7, 2, b'k', b'1']);
+    data.extend_from_slice(&[0x8, 1, 2, b'k', b'2']);
+    let batch = WriteBatch::from_data(&data);
+
+    let mut recorder = Recorder::default();
+    batch.iterate_cf(&mut recorder).unwrap();
+    assert_eq!(
+        recorder.ops,
+        [
+            ("single_delete", 0, b"k1".to_vec(), Vec::new()),
+            ("single_delete", 1, b"k2".to_vec(), Vec::new()),
+        ]
+    );
+
+    // A count that doesn't match the operations is reported as corruption.
+    data[8] = 3;
+    let batch = WriteBatch::from_data(&data);
+    let err = batch.iterate_cf(&mut Recorder::default()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Corruption);
+}
@@ -3,6 +3,7 @@
 use std::{
     cmp::Ordering,
     convert::TryInto,
+    fs,
     path::{Path, PathBuf},
 };
 
@@ -44,6 +45,12 @@ impl AsRef<Path> for &DBPath {
     }
 }
 
+/// Returns the contents of the info LOG of the database at `path`, which
+/// RocksDB starts with a dump of the options in effect.
+pub fn read_log(path: &DBPath) -> String {
+    fs::read_to_string(path.path.join("LOG")).expect("can read the LOG file")
+}
+
 type Pair = (Box<[u8]>, Box<[u8]>);
 
 pub fn pair(left: &[u8], right: &[u8]) -> Pair {